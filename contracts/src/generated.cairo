@@ -0,0 +1,2 @@
+/// `SP1_PROGRAM`, generated by `cargo run --release --bin vkey -- --write`.
+pub mod program_vkey;
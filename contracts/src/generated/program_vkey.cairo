@@ -0,0 +1,8 @@
+// @generated by `cargo run --release --bin vkey -- --write` in the script directory.
+// Do not edit by hand: regenerate after every change to the SP1 program.
+
+/// Verification key for the specific SP1 program this contract accepts.
+///
+/// This key uniquely identifies the SP1 program (Fibonacci computation in this example).
+/// Only proofs generated for this specific program will be accepted by the contract.
+pub const SP1_PROGRAM: u256 = 0x00ee2a4a1c9c659ed802a544aa469136e72e1a1538af94fce56705576b48f247;
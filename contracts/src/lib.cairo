@@ -13,6 +13,9 @@
 //! Deploy this contract and call `verify_sp1_proof` with properly formatted
 //! proof calldata generated by the SP1 proof generation scripts.
 
+/// Code generated from the SP1 program by the script crate.
+pub mod generated;
+
 /// Interface for SP1 proof verification on Starknet.
 /// 
 /// This interface provides methods to verify SP1 zero-knowledge proofs
@@ -67,14 +70,11 @@ mod HelloStarknet {
     const SP1_VERIFIER_CLASS_HASH: felt252 =
         0x5d147e9fcb648e847da819287b8f462ce9416419240c64d35640dcba35e127;
 
-    /// Verification key for the specific SP1 program this contract accepts.
-    /// 
-    /// This key uniquely identifies the SP1 program (Fibonacci computation in this example).
-    /// Only proofs generated for this specific program will be accepted by the contract.
-    /// 
-    /// **To update**: Run `cargo run --release --bin vkey` in the script directory
-    /// to generate the verification key for your SP1 program.
-    const SP1_PROGRAM: u256 = 0x00ee2a4a1c9c659ed802a544aa469136e72e1a1538af94fce56705576b48f247;
+    // Verification key for the specific SP1 program this contract accepts.
+    //
+    // Generated in `generated/program_vkey.cairo`. **To update**: Run
+    // `cargo run --release --bin vkey -- --write` in the script directory.
+    use crate::generated::program_vkey::SP1_PROGRAM;
 
     #[abi(embed_v0)]
     impl HelloStarknetImpl of super::IHelloStarknet<ContractState> {
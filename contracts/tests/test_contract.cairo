@@ -55,7 +55,7 @@ fn deploy_contract(name: ByteArray) -> ContractAddress {
 /// ## Prerequisites
 /// Before running this test, ensure you have:
 /// 1. Generated a proof: `cd ../script && cargo run --release --bin starknet -- --system groth16`
-/// 2. Updated the SP1_PROGRAM constant in the contract to match your verification key
+/// 2. Regenerated the SP1_PROGRAM constant with `cargo run --release --bin vkey -- --write`
 /// 
 /// ## Expected Behavior
 /// - The proof should verify successfully
@@ -1,4 +1,5 @@
 use alloy_sol_types::sol;
+use serde::{Deserialize, Serialize};
 
 sol! {
     /// The public values encoded as a struct that can be easily deserialized inside Solidity.
@@ -9,6 +10,17 @@ sol! {
     }
 }
 
+/// The input of the Fibonacci program, as written to `SP1Stdin`.
+///
+/// The script binaries read it from JSON or TOML files (`--input`), e.g.
+/// `{ "n": 10 }` or `n = 10`. Serialized with bincode, it is identical to the
+/// bare `u32` read by the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FibonacciInput {
+    /// Index of the Fibonacci number to compute.
+    pub n: u32,
+}
+
 /// Compute the n'th fibonacci number (wrapping around on overflows), using normal Rust code.
 pub fn fibonacci(n: u32) -> (u32, u32) {
     let mut a = 0u32;
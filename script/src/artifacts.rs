@@ -0,0 +1,83 @@
+//! # Proof Artifacts
+//!
+//! Binary copies of the full `SP1ProofWithPublicValues` and `SP1VerifyingKey`,
+//! saved next to the JSON fixtures.
+//!
+//! The fixtures only keep the hex encoding needed on Starknet. Keeping the
+//! original objects around makes it possible to verify a proof off-chain later,
+//! or to feed it back into the SP1 SDK (e.g. to re-wrap it).
+
+use crate::error::{Error, Result};
+use sp1_sdk::{SP1ProofWithPublicValues, SP1VerifyingKey};
+use std::path::{Path, PathBuf};
+
+/// File name of the verification key saved alongside the fixtures.
+///
+/// The key only depends on the program, so a single file is shared by every
+/// fixture in the directory.
+pub const VK_FILE_NAME: &str = "vk.bin";
+
+/// Paths of the artifacts written by [`save_proof_artifacts`].
+#[derive(Debug, Clone)]
+pub struct ProofArtifacts {
+    /// The bincode-encoded `SP1ProofWithPublicValues` (`{name}-proof.bin`).
+    pub proof_path: PathBuf,
+    /// The bincode-encoded `SP1VerifyingKey` ([`VK_FILE_NAME`]).
+    pub vk_path: PathBuf,
+}
+
+/// Save the proof and verification key in binary form.
+///
+/// The proof is written with `SP1ProofWithPublicValues::save`, so it can be read
+/// back with [`load_proof`] or directly through the SP1 SDK.
+pub fn save_proof_artifacts(
+    proof: &SP1ProofWithPublicValues,
+    vk: &SP1VerifyingKey,
+    out_dir: &Path,
+    name: &str,
+) -> Result<ProofArtifacts> {
+    std::fs::create_dir_all(out_dir).map_err(Error::fixture_io(out_dir))?;
+
+    let proof_path = out_dir.join(format!("{name}-proof.bin"));
+    proof.save(&proof_path).map_err(|source| Error::ProofIo {
+        path: proof_path.clone(),
+        source,
+    })?;
+
+    let vk_path = out_dir.join(VK_FILE_NAME);
+    save_vk(vk, &vk_path)?;
+
+    Ok(ProofArtifacts {
+        proof_path,
+        vk_path,
+    })
+}
+
+/// Load a proof saved with `SP1ProofWithPublicValues::save`.
+pub fn load_proof(path: impl AsRef<Path>) -> Result<SP1ProofWithPublicValues> {
+    let path = path.as_ref();
+    SP1ProofWithPublicValues::load(path).map_err(|source| Error::ProofIo {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Save a verification key with bincode.
+pub fn save_vk(vk: &SP1VerifyingKey, path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let bytes = bincode::serialize(vk).map_err(|source| Error::Bincode {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    std::fs::write(path, bytes).map_err(Error::fixture_io(path))
+}
+
+/// Load a verification key saved with [`save_vk`].
+pub fn load_vk(path: impl AsRef<Path>) -> Result<SP1VerifyingKey> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).map_err(Error::fixture_io(path))?;
+    bincode::deserialize(&bytes).map_err(|source| Error::Bincode {
+        path: path.to_path_buf(),
+        source,
+    })
+}
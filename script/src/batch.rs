@@ -0,0 +1,182 @@
+//! # Batch Proving
+//!
+//! Fixtures for many inputs of the same program, e.g. the edge cases `n = 0`,
+//! `n = 1` and `n = 47` (the first input whose result wraps around `u32`).
+//!
+//! `starknet batch` sets the program up once, proves every input with the same
+//! proving key and writes numbered fixtures (`{name}-{index:03}-fixture.json`)
+//! plus a [`BatchManifest`] (`manifest.json`) summarizing the runs.
+
+use crate::error::{Error, Result};
+use crate::fixture::{default_fixture_dir, FixtureOutput, ProofSystem};
+use crate::program::Program;
+use fibonacci_lib::FibonacciInput;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// File name of the batch manifest.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Directory batch fixtures are written to by default.
+///
+/// A `batch` subdirectory of [`default_fixture_dir`], so that batch runs do not
+/// overwrite the fixtures loaded by the Cairo tests.
+pub fn default_batch_dir() -> PathBuf {
+    default_fixture_dir().join("batch")
+}
+
+/// Base name of the fixture files of the `index`-th input of a batch.
+pub fn batch_fixture_name(base: &str, index: usize) -> String {
+    format!("{base}-{index:03}")
+}
+
+/// An inclusive range of Fibonacci indices given on the command line.
+///
+/// Accepts a single value (`47`), a half-open range (`0..10`) or an inclusive
+/// range (`0..=10`).
+///
+/// ## Example
+///
+/// ```rust
+/// use fibonacci_starknet::batch::InputRange;
+///
+/// let range: InputRange = "0..=3".parse().unwrap();
+/// assert_eq!(range.values().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+///
+/// let single: InputRange = "47".parse().unwrap();
+/// assert_eq!(single.values().collect::<Vec<_>>(), vec![47]);
+///
+/// assert!("5..5".parse::<InputRange>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputRange {
+    /// First value of the range.
+    pub start: u32,
+    /// Last value of the range, included.
+    pub end: u32,
+}
+
+impl InputRange {
+    /// The values of the range, in increasing order.
+    pub fn values(self) -> impl Iterator<Item = u32> {
+        self.start..=self.end
+    }
+}
+
+impl FromStr for InputRange {
+    type Err = String;
+
+    fn from_str(spec: &str) -> std::result::Result<Self, Self::Err> {
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<u32>()
+                .map_err(|error| format!("invalid input `{value}`: {error}"))
+        };
+
+        let (start, end) = if let Some((start, end)) = spec.split_once("..=") {
+            (parse(start)?, parse(end)?)
+        } else if let Some((start, end)) = spec.split_once("..") {
+            let end = parse(end)?
+                .checked_sub(1)
+                .ok_or_else(|| format!("empty input range `{spec}`"))?;
+            (parse(start)?, end)
+        } else {
+            let value = parse(spec)?;
+            (value, value)
+        };
+
+        if start > end {
+            return Err(format!("empty input range `{spec}`"));
+        }
+        Ok(Self { start, end })
+    }
+}
+
+/// One proven input of a batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchEntry {
+    /// Position of the input in the batch, used in the fixture file names.
+    pub index: usize,
+    /// The input written to the program stdin.
+    pub input: FibonacciInput,
+    /// Base name of the fixture files (`{fixture}-fixture.json`, ...).
+    pub fixture: String,
+    /// The public values committed by the program (hex string).
+    pub public_values: String,
+    /// Number of RISC-V cycles executed by the program.
+    pub cycles: u64,
+    /// Wall-clock time spent generating the proof, in milliseconds.
+    ///
+    /// `None` for mock fixtures, which are not proven, and cached proofs.
+    pub proof_time_ms: Option<u64>,
+    /// Whether the proof was taken from the proof cache (see [`crate::cache`]).
+    #[serde(default)]
+    pub cached: bool,
+    /// Number of calldata elements, or `None` if the proof system has no
+    /// Starknet calldata.
+    pub calldata_len: Option<usize>,
+}
+
+impl BatchEntry {
+    /// Describe the fixture written for an input under the base name `fixture`.
+    pub fn new(
+        index: usize,
+        input: FibonacciInput,
+        fixture: String,
+        output: &FixtureOutput,
+        cycles: u64,
+        proof_time: Option<Duration>,
+    ) -> Self {
+        Self {
+            index,
+            input,
+            fixture,
+            public_values: output.fixture.public_values.clone(),
+            cycles,
+            proof_time_ms: proof_time.map(|time| time.as_millis() as u64),
+            cached: false,
+            calldata_len: output.calldata_len,
+        }
+    }
+}
+
+/// Summary of a batch run, written as `manifest.json` next to its fixtures.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchManifest {
+    /// Name of the proven program.
+    pub program: String,
+    /// The program verification key hash, shared by every fixture.
+    pub vkey: String,
+    /// Lowercase name of the proof system, e.g. `groth16`.
+    pub system: String,
+    /// Whether the fixtures are mock fixtures generated without proving.
+    pub mock: bool,
+    /// The proven inputs, in command-line order.
+    pub entries: Vec<BatchEntry>,
+}
+
+impl BatchManifest {
+    /// An empty manifest for a batch of `program` proofs.
+    pub fn new(program: &Program, vkey: String, system: ProofSystem, mock: bool) -> Self {
+        Self {
+            program: program.name.clone(),
+            vkey,
+            system: system.name().to_string(),
+            mock,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Write the manifest to `out_dir`, returning its path.
+    pub fn write(&self, out_dir: &Path) -> Result<PathBuf> {
+        std::fs::create_dir_all(out_dir).map_err(Error::fixture_io(out_dir))?;
+
+        let path = out_dir.join(MANIFEST_FILE_NAME);
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .map_err(Error::fixture_io(&path))?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,216 @@
+//! # Cycle Budget
+//!
+//! A regression gate on the cycle counts of the guest program.
+//!
+//! Proving costs scale with cycles, so the `fibonacci bench` command executes the
+//! program over the fixed [`BENCH_INPUTS`] suite and compares the total and
+//! per-region cycle counts against a checked-in [`CycleBaseline`]. Any count
+//! growing by more than the allowed percentage fails the run with
+//! [`Error::CycleRegression`].
+
+use crate::error::{Error, Result};
+use crate::profile::cycle_regions;
+use crate::program::Program;
+use fibonacci_lib::FibonacciInput;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::ExecutionReport;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The inputs executed by `fibonacci bench`.
+///
+/// Covers the trivial loops (`0`, `1`, `2`), the default input and longer runs
+/// where the `fibonacci` region dominates.
+pub const BENCH_INPUTS: [u32; 6] = [0, 1, 2, 20, 100, 1000];
+
+/// Default allowed growth of a cycle count over its baseline, in percent.
+pub const DEFAULT_MAX_INCREASE_PERCENT: f64 = 5.0;
+
+/// Name of the metric holding the total cycle count in a [`CycleDelta`].
+pub const TOTAL_METRIC: &str = "total";
+
+/// Path of the checked-in cycle baseline.
+///
+/// The file is written by `fibonacci bench --save-baseline`, which needs the SP1
+/// toolchain to build the guest, and must be recorded again after intentional
+/// guest changes.
+///
+/// Like [`crate::fixture::default_fixture_dir`], this path is resolved at
+/// compile time and only makes sense in a checkout of the repository.
+pub fn default_baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("cycle-baseline.json")
+}
+
+/// The cycle counts of one execution of the program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleMeasurement {
+    /// The program input.
+    pub input: FibonacciInput,
+    /// Total number of cycles.
+    pub total_cycles: u64,
+    /// Cycles spent in each `cycle-tracker` region.
+    pub regions: BTreeMap<String, u64>,
+}
+
+impl CycleMeasurement {
+    /// The cycle counts of an execution on `input`.
+    pub fn new(input: FibonacciInput, report: &ExecutionReport) -> Self {
+        Self {
+            input,
+            total_cycles: report.total_instruction_count(),
+            regions: cycle_regions(report)
+                .into_iter()
+                .map(|region| (region.name, region.cycles))
+                .collect(),
+        }
+    }
+}
+
+/// The reference cycle counts of a program over the bench suite.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleBaseline {
+    /// Name of the measured program.
+    pub program: String,
+    /// The `0x`-prefixed SHA-256 digest of the measured ELF.
+    pub elf_sha256: String,
+    /// One measurement per bench input.
+    pub measurements: Vec<CycleMeasurement>,
+}
+
+/// The change of one cycle count relative to the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleDelta {
+    /// The program input.
+    pub n: u32,
+    /// [`TOTAL_METRIC`] or the name of a `cycle-tracker` region.
+    pub metric: String,
+    /// The baseline count, or `None` for inputs and regions it does not cover.
+    pub baseline: Option<u64>,
+    /// The current count.
+    pub current: u64,
+    /// Whether the count grew past the allowed increase.
+    pub regressed: bool,
+}
+
+impl CycleDelta {
+    /// Growth of the count over the baseline, in percent.
+    ///
+    /// `None` without a baseline count, or when the baseline count is zero.
+    pub fn change_percent(&self) -> Option<f64> {
+        let baseline = self.baseline.filter(|&baseline| baseline > 0)?;
+        Some((self.current as f64 - baseline as f64) * 100.0 / baseline as f64)
+    }
+}
+
+impl CycleBaseline {
+    /// A baseline of `program` from the given measurements.
+    pub fn new(program: &Program, measurements: Vec<CycleMeasurement>) -> Self {
+        Self {
+            program: program.name.clone(),
+            elf_sha256: program.elf_sha256(),
+            measurements,
+        }
+    }
+
+    /// Read a baseline written by [`CycleBaseline::write`].
+    ///
+    /// Fails with [`Error::MissingCycleBaseline`] if `path` does not exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|error| {
+            if error.kind() == std::io::ErrorKind::NotFound {
+                Error::MissingCycleBaseline {
+                    path: path.to_path_buf(),
+                }
+            } else {
+                Error::fixture_io(path)(error)
+            }
+        })?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Write the baseline to `path` as JSON.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(Error::fixture_io(dir))?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json + "\n").map_err(Error::fixture_io(path))
+    }
+
+    /// Compare `current` measurements against the baseline.
+    ///
+    /// Returns the total and every region of every measurement, flagging counts
+    /// that grew by more than `max_increase_percent`. Inputs and regions missing
+    /// from the baseline are reported without being flagged.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use fibonacci_lib::FibonacciInput;
+    /// use fibonacci_starknet::bench::{CycleBaseline, CycleMeasurement};
+    ///
+    /// let measurement = |total_cycles| CycleMeasurement {
+    ///     input: FibonacciInput { n: 20 },
+    ///     total_cycles,
+    ///     regions: Default::default(),
+    /// };
+    /// let baseline = CycleBaseline {
+    ///     program: "fibonacci-program".to_string(),
+    ///     elf_sha256: "0x00".to_string(),
+    ///     measurements: vec![measurement(1000)],
+    /// };
+    ///
+    /// assert!(!baseline.compare(&[measurement(1040)], 5.0)[0].regressed);
+    /// assert!(baseline.compare(&[measurement(1060)], 5.0)[0].regressed);
+    /// ```
+    pub fn compare(
+        &self,
+        current: &[CycleMeasurement],
+        max_increase_percent: f64,
+    ) -> Vec<CycleDelta> {
+        let mut deltas = Vec::new();
+        for measurement in current {
+            let n = measurement.input.n;
+            let baseline = self
+                .measurements
+                .iter()
+                .find(|baseline| baseline.input == measurement.input);
+
+            let delta = |metric: &str, baseline: Option<u64>, current: u64| CycleDelta {
+                n,
+                metric: metric.to_string(),
+                baseline,
+                current,
+                regressed: baseline.is_some_and(|baseline| {
+                    current as f64 > baseline as f64 * (1.0 + max_increase_percent / 100.0)
+                }),
+            };
+
+            deltas.push(delta(
+                TOTAL_METRIC,
+                baseline.map(|baseline| baseline.total_cycles),
+                measurement.total_cycles,
+            ));
+            for (region, &cycles) in &measurement.regions {
+                let region_baseline =
+                    baseline.and_then(|baseline| baseline.regions.get(region).copied());
+                deltas.push(delta(region, region_baseline, cycles));
+            }
+        }
+        deltas
+    }
+}
+
+/// Fail with [`Error::CycleRegression`] if any delta regressed.
+pub fn check_deltas(deltas: &[CycleDelta], max_increase_percent: f64) -> Result<()> {
+    let regressions = deltas.iter().filter(|delta| delta.regressed).count();
+    if regressions > 0 {
+        return Err(Error::CycleRegression {
+            regressions,
+            max_increase_percent,
+        });
+    }
+    Ok(())
+}
@@ -18,11 +18,52 @@
 //! cargo run --release -- --prove --n 10
 //! ```
 //!
+//! ### Read the program input from a JSON or TOML file:
+//! ```bash
+//! cargo run --release -- --execute --input input.json
+//! ```
+//!
+//! ### Generate a new proof instead of reusing a cached one:
+//! ```bash
+//! cargo run --release -- --prove --n 10 --no-cache
+//! ```
+//!
+//! ### Print a machine-readable report of the run for CI:
+//! ```bash
+//! cargo run --release -- --execute --n 10 --json | jq .cycles
+//! ```
+//!
+//! ### Profile syscalls, opcodes and cycle-tracker sections before proving:
+//! ```bash
+//! cargo run --release -- --execute --n 10 --profile
+//! ```
+//!
+//! ### Fail if the guest cycle counts grew past the checked-in baseline:
+//! ```bash
+//! cargo run --release -- bench --max-increase 5
+//! ```
+//!
+//! ### Abort runaway executions, e.g. for a very large `--n`:
+//! ```bash
+//! cargo run --release -- --execute --n 4000000000 --max-cycles 100000000
+//! ```
+//!
+//! ### Run an ELF built elsewhere instead of the embedded program:
+//! ```bash
+//! cargo run --release -- --execute --elf ../other/elf/program --n 10
+//! ```
+//!
 //! ## Features
 //!
-//! - **Configurable Input**: Specify the Fibonacci number to compute via `--n` parameter
+//! - **Configurable Input**: Specify the Fibonacci number to compute via `--n`, a
+//!   JSON/TOML `--input` file, or raw `--input-bytes`
 //! - **Execution Verification**: Validates computation results against expected values
-//! - **Cycle Counting**: Reports the number of execution cycles for performance analysis
+//! - **Cycle Counting**: Reports the number of execution cycles, split by guest
+//!   `cycle-tracker` region, for performance analysis
+//! - **Profiling**: Breaks cycles down by syscall, precompile, opcode and `cycle-tracker`
+//!   section, and exports the section totals in folded format
+//! - **Cycle Budget**: `bench` compares cycle counts over a fixed input suite against
+//!   a checked-in baseline
 //! - **Proof Generation**: Creates verifiable proofs of correct computation
 //!
 //! ## Zero-Knowledge Properties
@@ -33,16 +74,22 @@
 //! - **Zero-Knowledge**: Proofs reveal only the public outputs, not computation steps
 
 use alloy_sol_types::SolType;
-use clap::Parser;
-use fibonacci_lib::PublicValuesStruct;
-use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
-
-/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
-///
-/// This binary contains the compiled SP1 Fibonacci program that will be executed
-/// within the zero-knowledge virtual machine. The program is embedded at compile
-/// time using the `include_elf!` macro.
-pub const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci-program");
+use clap::{Parser, Subcommand};
+use fibonacci_lib::{FibonacciInput, PublicValuesStruct};
+use fibonacci_starknet::bench::{
+    check_deltas, default_baseline_path, CycleBaseline, CycleDelta, CycleMeasurement, BENCH_INPUTS,
+    DEFAULT_MAX_INCREASE_PERCENT,
+};
+use fibonacci_starknet::cache::{KeyCache, ProofCache, ProofCacheKey};
+use fibonacci_starknet::input::ProgramInput;
+use fibonacci_starknet::preflight::execute;
+use fibonacci_starknet::profile::{default_profile_dir, CycleRegion, ExecutionProfile};
+use fibonacci_starknet::program::Program;
+use fibonacci_starknet::report::{timed, RunReport};
+use fibonacci_starknet::{status, Error, ProofSystem, Result};
+use sp1_sdk::{Prover, ProverClient};
+use std::path::PathBuf;
+use std::process::ExitCode;
 
 /// Command-line arguments for the SP1 Fibonacci demonstration.
 ///
@@ -52,6 +99,9 @@ pub const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci-program");
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Execute the program without generating a proof.
     ///
     /// This mode runs the SP1 program in the zkVM and displays the results
@@ -78,6 +128,116 @@ struct Args {
     /// Default: 20 (computes F(19)=4181 and F(20)=6765)
     #[arg(long, default_value = "20")]
     n: u32,
+
+    /// A JSON or TOML file holding the program input, e.g. `{ "n": 10 }`.
+    ///
+    /// The file is parsed as a `fibonacci_lib::FibonacciInput` and written to
+    /// the program stdin in place of `--n`.
+    #[arg(long, value_name = "FILE", conflicts_with = "n")]
+    input: Option<PathBuf>,
+
+    /// A file written to the program stdin as raw bytes, in place of `--n`.
+    ///
+    /// Fibonacci result checks are skipped; the raw public values are printed instead.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["n", "input"])]
+    input_bytes: Option<PathBuf>,
+
+    /// Path of an SP1 program ELF to run instead of the embedded Fibonacci program.
+    ///
+    /// The program receives the input of `--n`, `--input` or `--input-bytes`.
+    /// Fibonacci result checks are skipped for external programs; their raw
+    /// public values are printed instead.
+    #[arg(long, value_name = "FILE")]
+    elf: Option<PathBuf>,
+
+    /// Always generate a new proof, without reading or writing the proof cache.
+    ///
+    /// By default, `--prove` reuses the proof of a previous run with the same
+    /// program, input and SP1 version.
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Directory of the proof cache.
+    ///
+    /// Defaults to `target/proof-cache` in the repository checkout this binary
+    /// was built from.
+    #[arg(long, value_name = "DIR", env = "SP1_PROOF_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Always run the program setup, without reading or writing the key cache.
+    #[arg(long)]
+    no_key_cache: bool,
+
+    /// Directory of the proving and verifying key cache.
+    ///
+    /// Defaults to `target/key-cache` in the repository checkout this binary
+    /// was built from.
+    #[arg(long, value_name = "DIR", env = "SP1_KEY_CACHE_DIR")]
+    key_cache_dir: Option<PathBuf>,
+
+    /// Write a JSON report of the run (input, public values, cycles, stage
+    /// durations and proof size) to this file.
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+
+    /// Print the JSON report of the run to stdout.
+    ///
+    /// Progress messages are sent to stderr so that stdout can be piped.
+    #[arg(long)]
+    json: bool,
+
+    /// Print and save a breakdown of the execution: syscall counts, precompile
+    /// usage, opcode histogram and `cycle-tracker` section costs.
+    ///
+    /// The profile is saved as `{program}-profile.json`, and the section totals
+    /// in folded format as `{program}-sections.folded`. Only per-section totals
+    /// are exported: the file is not a trace, and holds no call stacks to render
+    /// as a flamegraph.
+    #[arg(long, requires = "execute")]
+    profile: bool,
+
+    /// Directory the profile is written to.
+    ///
+    /// Defaults to `target/profile` in the repository checkout this binary was
+    /// built from.
+    #[arg(long, value_name = "DIR", requires = "profile")]
+    profile_dir: Option<PathBuf>,
+
+    /// Abort execution and proving after this many cycles.
+    ///
+    /// Guards against inputs that make the program loop for hours, e.g. a large `--n`.
+    #[arg(long, value_name = "CYCLES")]
+    max_cycles: Option<u64>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Execute the program over a fixed input suite and compare its cycle counts
+    /// against a checked-in baseline.
+    ///
+    /// Fails with exit code 17 when a total or per-region cycle count grew by
+    /// more than `--max-increase` percent.
+    Bench(BenchArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct BenchArgs {
+    /// The baseline file.
+    ///
+    /// Defaults to `script/cycle-baseline.json` in the repository checkout this
+    /// binary was built from.
+    #[arg(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+
+    /// Allowed growth of each cycle count over the baseline, in percent.
+    #[arg(long, value_name = "PERCENT", default_value_t = DEFAULT_MAX_INCREASE_PERCENT)]
+    max_increase: f64,
+
+    /// Write the current cycle counts to the baseline file instead of comparing.
+    ///
+    /// Run after intentional guest changes and check the file in.
+    #[arg(long)]
+    save_baseline: bool,
 }
 
 /// Main entry point for the SP1 Fibonacci demonstration.
@@ -97,51 +257,121 @@ struct Args {
 /// - Proof generation fails
 /// - Proof verification fails
 /// - Computation results don't match expected values
-fn main() {
-    // Initialize logging for detailed execution information
-    sp1_sdk::utils::setup_logger();
-
+///
+/// Pipeline failures exit with the code of the stage that failed (see
+/// `Error::exit_code`), like the `starknet` and `vkey` binaries.
+fn main() -> ExitCode {
     // Load environment variables from .env file if present
     dotenv::dotenv().ok();
 
     // Parse and validate command-line arguments
     let args = Args::parse();
 
+    if args.json {
+        // Keep stdout reserved for the JSON report. The SP1 logger writes to
+        // stdout, so it is left disabled in this mode.
+        fibonacci_starknet::console::redirect_to_stderr();
+    } else {
+        // Initialize logging for detailed execution information
+        sp1_sdk::utils::setup_logger();
+    }
+
     // Ensure exactly one mode is selected
-    if args.execute == args.prove {
+    if args.command.is_some() && (args.execute || args.prove) {
+        eprintln!("Error: --execute and --prove cannot be combined with a subcommand");
+        return ExitCode::FAILURE;
+    }
+    if args.command.is_none() && args.execute == args.prove {
         eprintln!("Error: You must specify either --execute or --prove");
-        std::process::exit(1);
+        return ExitCode::FAILURE;
     }
 
+    let result = match &args.command {
+        Some(Command::Bench(bench_args)) => bench(&args, bench_args),
+        None => run(&args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+/// Execute or prove the program, as selected by `--execute` and `--prove`.
+fn run(args: &Args) -> Result<()> {
+    // Load the program, falling back to the embedded Fibonacci ELF
+    let program = Program::from_elf_arg(args.elf.as_deref())?;
+
+    // Load the program input from --n, --input or --input-bytes
+    let input =
+        ProgramInput::from_args(args.n, args.input.as_deref(), args.input_bytes.as_deref())?;
+
     // Initialize the SP1 prover client
     // This client handles communication with the zkVM and proof generation
     let client = ProverClient::from_env();
 
     // Prepare program inputs
-    // The SP1 program expects a single u32 input representing the Fibonacci index
-    let mut stdin = SP1Stdin::new();
-    stdin.write(&args.n);
+    // The Fibonacci program expects a single u32 input representing the Fibonacci index
+    let stdin = input.to_stdin();
+
+    let mut run_report = RunReport::new(if args.execute { "execute" } else { "prove" });
+    run_report.set_program(&program);
+    run_report.input = Some(input.clone());
 
-    println!("n: {}", args.n);
+    status!("Input: {}", input.describe());
 
     if args.execute {
         // Execute the program
-        println!("🚀 Executing SP1 program...");
-        let (output, report) = client.execute(FIBONACCI_ELF, &stdin).run().unwrap();
-        println!("✅ Program executed successfully.");
+        status!("🚀 Executing SP1 program...");
+        let (execution, execute_ms) = timed(|| execute(&client, &program, &stdin, args.max_cycles));
+        let (output, report) = execution?;
+        status!("✅ Program executed successfully.");
+
+        run_report.set_public_values(output.as_slice());
+        run_report.set_execution(&report);
+        run_report.durations.execute_ms = Some(execute_ms);
+
+        if args.profile {
+            let profile = ExecutionProfile::from_report(&program.name, &report);
+            print_profile(&profile);
+
+            let profile_dir = args.profile_dir.clone().unwrap_or_else(default_profile_dir);
+            let files = profile.write(&profile_dir, &program.name)?;
+            status!("💾 Profile saved to: {}", files.json_path.display());
+            status!(
+                "💾 Section totals saved to: {}",
+                files.sections_path.display()
+            );
+            run_report.files.push(files.json_path);
+            run_report.files.push(files.sections_path);
+        }
+
+        let expected_input = input.fibonacci().filter(|_| program.is_embedded());
+        let Some(FibonacciInput { n }) = expected_input else {
+            // The Fibonacci checks below only apply to the embedded program
+            // and its typed input
+            status!("📊 Public Values: 0x{}", hex::encode(output.as_slice()));
+            status!("📈 Execution Statistics:");
+            status!("  Total cycles: {}", report.total_instruction_count());
+            print_cycle_regions(&run_report.regions, report.total_instruction_count());
+            return emit_report(args, &run_report);
+        };
 
         // Read the output.
         let decoded = PublicValuesStruct::abi_decode(output.as_slice()).unwrap();
         let PublicValuesStruct { n: result_n, a, b } = decoded;
 
         // Display the computation results
-        println!("📊 Computation Results:");
-        println!("  Input (n): {}", result_n);
-        println!("  F(n-1): {}", a);
-        println!("  F(n): {}", b);
+        status!("📊 Computation Results:");
+        status!("  Input (n): {}", result_n);
+        status!("  F(n-1): {}", a);
+        status!("  F(n): {}", b);
 
         // Validate the results against expected values
-        let (expected_a, expected_b) = fibonacci_lib::fibonacci(args.n);
+        let (expected_a, expected_b) = fibonacci_lib::fibonacci(n);
         assert_eq!(
             a, expected_a,
             "F(n-1) mismatch: expected {}, got {}",
@@ -153,39 +383,240 @@ fn main() {
             expected_b, b
         );
         assert_eq!(
-            result_n, args.n,
+            result_n, n,
             "Input mismatch: expected {}, got {}",
-            args.n, result_n
+            n, result_n
         );
 
-        println!("✅ Values are correct!");
+        status!("✅ Values are correct!");
 
         // Report execution statistics
-        println!("📈 Execution Statistics:");
-        println!("  Total cycles: {}", report.total_instruction_count());
+        status!("📈 Execution Statistics:");
+        status!("  Total cycles: {}", report.total_instruction_count());
+        print_cycle_regions(&run_report.regions, report.total_instruction_count());
     } else {
         // Setup the program for proving.
-        println!("🔧 Setting up proving system...");
-        let (pk, vk) = client.setup(FIBONACCI_ELF);
-        println!("✅ Setup complete.");
+        status!("🔧 Setting up proving system...");
+        let (keys, setup_ms) = timed(|| {
+            if args.no_key_cache {
+                return Ok(client.setup(&program.elf));
+            }
+            // Reuse the keys of a previous run of the same program
+            let key_cache = args
+                .key_cache_dir
+                .clone()
+                .map_or_else(KeyCache::default, KeyCache::new);
+            key_cache.setup(&program, client.version(), |elf| client.setup(elf))
+        });
+        let (pk, vk) = keys?;
+        run_report.durations.setup_ms = Some(setup_ms);
+        status!("✅ Setup complete.");
+
+        // Proving does not report cycles, so execute the program first for the report
+        if args.report.is_some() || args.json {
+            let (execution, execute_ms) =
+                timed(|| execute(&client, &program, &stdin, args.max_cycles));
+            let (_, report) = execution?;
+            run_report.set_execution(&report);
+            run_report.durations.execute_ms = Some(execute_ms);
+        }
+
+        // Reuse the proof of a previous run with the same program and input
+        let cache = (!args.no_cache).then(|| {
+            args.cache_dir
+                .clone()
+                .map_or_else(ProofCache::default, ProofCache::new)
+        });
+        let key = ProofCacheKey::new(&program, &stdin, ProofSystem::Core, client.version())?;
 
-        // Generate the proof
-        println!("🔐 Generating proof...");
-        let proof = client
-            .prove(&pk, &stdin)
-            .run()
-            .expect("failed to generate proof");
+        let (proof, prove_ms) = timed(|| match cache.as_ref().and_then(|cache| cache.load(&key)) {
+            Some(proof) => {
+                status!("♻️  Using cached proof (pass --no-cache to prove again)");
+                run_report.cached_proof = true;
+                Ok(proof)
+            }
+            None => {
+                // Generate the proof
+                status!("🔐 Generating proof...");
+                let mut builder = client.prove(&pk, &stdin);
+                if let Some(max_cycles) = args.max_cycles {
+                    builder = builder.cycle_limit(max_cycles);
+                }
+                let proof = builder.run().map_err(Error::Prover)?;
 
-        println!("✅ Successfully generated proof!");
+                status!("✅ Successfully generated proof!");
+
+                if let Some(cache) = &cache {
+                    let path = cache.store(&key, &proof)?;
+                    status!("💾 Cached proof: {}", path.display());
+                }
+                Ok::<_, Error>(proof)
+            }
+        });
+        let proof = proof?;
+        run_report.durations.prove_ms = Some(prove_ms);
+        run_report.set_proof(&proof, ProofSystem::Core);
 
         // Verify the proof.
-        println!("🔍 Verifying proof...");
-        client.verify(&proof, &vk).expect("failed to verify proof");
-        println!("✅ Successfully verified proof!");
+        status!("🔍 Verifying proof...");
+        client.verify(&proof, &vk)?;
+        status!("✅ Successfully verified proof!");
 
         // Note about proof types
-        println!("💡 Note: This is a 'core' proof suitable for development.");
-        println!("   For on-chain verification, use the Starknet-specific script:");
-        println!("   cargo run --release --bin starknet -- --system groth16");
+        status!("💡 Note: This is a 'core' proof suitable for development.");
+        status!("   For on-chain verification, use the Starknet-specific script:");
+        status!("   cargo run --release --bin starknet -- --system groth16");
+    }
+
+    emit_report(args, &run_report)
+}
+
+/// Execute the program over [`BENCH_INPUTS`] and check its cycle budget.
+fn bench(args: &Args, bench_args: &BenchArgs) -> Result<()> {
+    let program = Program::from_elf_arg(args.elf.as_deref())?;
+    let client = ProverClient::from_env();
+
+    // Load the baseline first, so that a missing one fails before executing
+    let baseline_path = bench_args
+        .baseline
+        .clone()
+        .unwrap_or_else(default_baseline_path);
+    let baseline = if bench_args.save_baseline {
+        None
+    } else {
+        Some(CycleBaseline::load(&baseline_path)?)
+    };
+
+    status!("🚀 Executing {} bench inputs...", BENCH_INPUTS.len());
+    let measurements = BENCH_INPUTS
+        .into_iter()
+        .map(|n| {
+            let input = FibonacciInput { n };
+            let stdin = ProgramInput::Fibonacci(input).to_stdin();
+            let (_, report) = execute(&client, &program, &stdin, args.max_cycles)?;
+            Ok(CycleMeasurement::new(input, &report))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let Some(baseline) = baseline else {
+        CycleBaseline::new(&program, measurements).write(&baseline_path)?;
+        status!("💾 Cycle baseline saved to: {}", baseline_path.display());
+        return Ok(());
+    };
+    if baseline.elf_sha256 != program.elf_sha256() {
+        status!("ℹ️  The baseline was recorded for a different ELF.");
+    }
+
+    let deltas = baseline.compare(&measurements, bench_args.max_increase);
+    print_cycle_deltas(&deltas);
+    check_deltas(&deltas, bench_args.max_increase)?;
+
+    status!(
+        "✅ Cycle counts are within {}% of the baseline.",
+        bench_args.max_increase
+    );
+    Ok(())
+}
+
+/// Print the comparison of the bench cycle counts with the baseline.
+fn print_cycle_deltas(deltas: &[CycleDelta]) {
+    status!("📈 Cycle Budget:");
+    status!(
+        "    {:>6} {:<16} {:>12} {:>12} {:>9}",
+        "n",
+        "Metric",
+        "Baseline",
+        "Current",
+        "Change"
+    );
+    for delta in deltas {
+        let baseline = delta
+            .baseline
+            .map_or_else(|| "-".to_string(), |cycles| cycles.to_string());
+        let change = match delta.change_percent() {
+            Some(change) => format!("{change:+.1}%"),
+            None if delta.baseline.is_none() => "new".to_string(),
+            None => "-".to_string(),
+        };
+        let marker = if delta.regressed { " ❌" } else { "" };
+        status!(
+            "    {:>6} {:<16} {:>12} {:>12} {:>9}{}",
+            delta.n,
+            delta.metric,
+            baseline,
+            delta.current,
+            change,
+            marker
+        );
+    }
+}
+
+/// Print the cycles spent in each `cycle-tracker` region of the program.
+fn print_cycle_regions(regions: &[CycleRegion], total_cycles: u64) {
+    if regions.is_empty() {
+        return;
+    }
+    status!("  Cycles by region:");
+    status!(
+        "    {:<16} {:>12} {:>7} {:>6}",
+        "Region",
+        "Cycles",
+        "Share",
+        "Calls"
+    );
+    for region in regions {
+        let share = region.cycles as f64 * 100.0 / total_cycles.max(1) as f64;
+        status!(
+            "    {:<16} {:>12} {:>6.1}% {:>6}",
+            region.name,
+            region.cycles,
+            share,
+            region.invocations
+        );
+    }
+}
+
+/// Number of entries shown for each histogram of `--profile`.
+const PROFILE_TOP_ENTRIES: usize = 10;
+
+/// Print the breakdown of an execution requested with `--profile`.
+fn print_profile(profile: &ExecutionProfile) {
+    status!("🔬 Execution Profile:");
+    status!("  Total cycles: {}", profile.total_cycles);
+    status!("  Total syscalls: {}", profile.total_syscalls);
+    status!(
+        "  Touched memory addresses: {}",
+        profile.touched_memory_addresses
+    );
+
+    let sections = [
+        ("cycle-tracker sections (cycles)", &profile.sections),
+        ("Syscalls (calls)", &profile.syscalls),
+        ("Precompiles (calls)", &profile.precompiles),
+        ("Opcodes (executions)", &profile.opcodes),
+    ];
+    for (title, histogram) in sections {
+        if histogram.is_empty() {
+            continue;
+        }
+        status!("  {}:", title);
+        for (name, count) in ExecutionProfile::top(histogram, PROFILE_TOP_ENTRIES) {
+            status!("    {:<32} {:>12}", name, count);
+        }
+        if histogram.len() > PROFILE_TOP_ENTRIES {
+            status!("    ... and {} more", histogram.len() - PROFILE_TOP_ENTRIES);
+        }
+    }
+}
+
+/// Write and print the run report requested with `--report` and `--json`.
+fn emit_report(args: &Args, run_report: &RunReport) -> Result<()> {
+    if let Some(path) = &args.report {
+        run_report.write(path)?;
+        status!("📝 Run report saved to: {}", path.display());
+    }
+    if args.json {
+        println!("{}", run_report.to_json()?);
     }
+    Ok(())
 }
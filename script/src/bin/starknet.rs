@@ -23,11 +23,88 @@
 //! SP1_PROVER=network NETWORK_PRIVATE_KEY=your_key cargo run --release --bin starknet
 //! ```
 //!
+//! ### Mock fixtures for offline CI (no proving, marked `"mock": true`):
+//! ```bash
+//! starknet --mock --n 10 --out-dir ./fixtures
+//! ```
+//!
+//! ### Read the program input from a JSON or TOML file:
+//! ```bash
+//! starknet --input input.toml
+//! ```
+//!
+//! ### Prove an ELF built elsewhere instead of the embedded program:
+//! ```bash
+//! starknet --elf ../other/elf/program --n 10
+//! ```
+//!
+//! ### Write fixtures outside the repository checkout:
+//! ```bash
+//! starknet --n 10 --out-dir ./fixtures --fixture-name fib-10
+//! ```
+//!
+//! ### Print the fixture JSON without touching the filesystem:
+//! ```bash
+//! starknet --n 10 --no-fixture --stdout > fixture.json
+//! ```
+//!
+//! ### Regenerate calldata from a saved proof or fixture (no proving):
+//! ```bash
+//! starknet convert --fixture ../contracts/src/fixtures/groth16-fixture.json --calldata-encoding json
+//! starknet convert --proof groth16-proof.bin --calldata-encoding starkli
+//! ```
+//!
+//! ### Iterate quickly with compressed proofs, then wrap to Groth16:
+//! ```bash
+//! starknet --system compressed --n 10
+//! starknet convert --proof ../contracts/src/fixtures/compressed-proof.bin --wrap groth16
+//! ```
+//!
+//! ### Prove many inputs with a single setup, plus a `manifest.json`:
+//! ```bash
+//! starknet batch --n 0,1,47 --n 90..=100 --out-dir ./fixtures/batch
+//! ```
+//!
+//! ### Force a new proof, or clear the proof cache:
+//! ```bash
+//! starknet --n 10 --no-cache
+//! starknet cache prune --max-age-days 7
+//! ```
+//!
+//! ### Write a machine-readable report of the run for CI:
+//! ```bash
+//! starknet --n 10 --report run-report.json
+//! starknet --n 10 --json | jq .calldata_len
+//! ```
+//!
+//! ### Cap execution, and prove a long-running input anyway:
+//! ```bash
+//! starknet --n 100000000 --max-cycles 2000000000 --force
+//! ```
+//!
+//! ### Decode calldata and check it against its fixture:
+//! ```bash
+//! starknet decode-calldata --calldata ../contracts/src/fixtures/groth16-calldata.txt
+//! ```
+//!
+//! ### Verify a saved proof off-chain:
+//! ```bash
+//! starknet verify --proof ../contracts/src/fixtures/groth16-proof.bin
+//! ```
+//!
 //! ## Output Files
 //!
-//! The script generates test fixtures in `../contracts/src/fixtures/`:
+//! By default, the script generates test fixtures in `../contracts/src/fixtures/`
+//! (override with `--out-dir` and `--fixture-name`):
 //! - `groth16-fixture.json`: Complete proof data with metadata
 //! - `groth16-calldata.txt`: Formatted calldata for Starknet contract calls
+//! - `groth16-proof.bin`: The full SP1 proof, for off-chain verification
+//! - `vk.bin`: The SP1 verification key of the program
+//!
+//! PLONK runs (`--system plonk`) write `plonk-fixture.json` and the binary proof,
+//! then exit with a "not supported on Starknet" error since Garaga has no SP1
+//! PLONK verifier. Core and compressed runs write a fixture marked
+//! `"onchain_verifiable": false` and the binary proof, without any calldata.
 //!
 //! ## Hardware Requirements
 //!
@@ -39,29 +116,270 @@
 //! The generated calldata can be used directly with the Starknet verification contract
 //! to test and verify SP1 proofs on-chain.
 
-use clap::{Parser, ValueEnum};
-use garaga_rs::calldata::full_proof_with_hints::groth16::{
-    get_groth16_calldata, get_sp1_vk, Groth16Proof,
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use fibonacci_lib::FibonacciInput;
+use fibonacci_starknet::artifacts::{load_proof, load_vk, VK_FILE_NAME};
+use fibonacci_starknet::batch::{
+    batch_fixture_name, default_batch_dir, BatchEntry, BatchManifest, InputRange,
+};
+use fibonacci_starknet::cache::{KeyCache, ProofCache, ProofCacheKey};
+use fibonacci_starknet::decode::{
+    decode_groth16_calldata, parse_hex_calldata, DecodedCalldata, SectionCheck,
+};
+use fibonacci_starknet::fixture::default_fixture_dir;
+use fibonacci_starknet::input::{read_input_file, ProgramInput};
+use fibonacci_starknet::preflight::{
+    execute, execute_within_budget, ProvingEstimate, DEFAULT_MAX_PROVE_CYCLES,
 };
-use garaga_rs::definitions::CurveID;
-use num_bigint::BigUint;
-use serde::{Deserialize, Serialize};
+use fibonacci_starknet::program::Program;
+use fibonacci_starknet::report::{timed, RunReport};
+use fibonacci_starknet::wrap::wrap_groth16;
+use fibonacci_starknet::{
+    convert_fixture, create_mock_fixture, create_proof_fixture, status, CalldataEncoding,
+    CalldataFormat, Error, FixtureOptions, FixtureOutput, ProofSystem, Result,
+    SP1FibonacciProofFixture,
+};
+use serde::Serialize;
 use sp1_sdk::{
-    include_elf, HashableKey, ProverClient, SP1ProofWithPublicValues, SP1Stdin, SP1VerifyingKey,
+    EnvProver, ExecutionReport, HashableKey, Prover, ProverClient, SP1ProofWithPublicValues,
+    SP1ProvingKey, SP1PublicValues, SP1Stdin, SP1VerifyingKey,
 };
 use std::path::PathBuf;
-
-/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
-///
-/// This is the compiled SP1 Fibonacci program that will be proven and verified.
-pub const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci-program");
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
 /// Command-line arguments for Starknet proof generation.
 ///
 /// This structure defines the available options for generating Starknet-compatible proofs.
+/// Without a subcommand, the binary generates a new proof.
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
 struct StarknetArgs {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    prove: ProveArgs,
+}
+
+impl StarknetArgs {
+    /// The output options of the selected command, if it writes fixtures.
+    fn output(&self) -> Option<&OutputArgs> {
+        match &self.command {
+            None => Some(&self.prove.output),
+            Some(Command::Convert(args)) => Some(&args.output),
+            Some(Command::Batch(args)) => Some(&args.output),
+            Some(Command::Verify(_))
+            | Some(Command::DecodeCalldata(_))
+            | Some(Command::Cache(_)) => None,
+        }
+    }
+
+    /// The report options of the selected command, if it supports run reports.
+    fn report(&self) -> Option<&ReportArgs> {
+        match &self.command {
+            None => Some(&self.prove.report),
+            Some(Command::Convert(args)) => Some(&args.report),
+            _ => None,
+        }
+    }
+
+    /// Whether the selected command prints JSON on stdout.
+    fn prints_json(&self) -> bool {
+        matches!(self.command, Some(Command::DecodeCalldata(_)))
+            || self.output().is_some_and(|output| output.stdout)
+            || self.report().is_some_and(|report| report.json)
+    }
+}
+
+/// Additional operations on previously generated proofs.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Convert a saved proof or fixture to Starknet calldata without proving again.
+    Convert(ConvertArgs),
+
+    /// Prove many inputs with a single setup and write numbered fixtures and a manifest.
+    Batch(BatchArgs),
+
+    /// Verify a saved proof off-chain against its verification key.
+    Verify(VerifyArgs),
+
+    /// Decode a calldata file into JSON and check it against its fixture.
+    DecodeCalldata(DecodeCalldataArgs),
+
+    /// Manage the cache of generated proofs.
+    #[command(subcommand)]
+    Cache(CacheCommand),
+}
+
+/// Operations on the proof cache.
+#[derive(Subcommand, Debug)]
+enum CacheCommand {
+    /// Remove cached proofs.
+    Prune(PruneArgs),
+}
+
+/// Options for pruning the proof cache.
+#[derive(Args, Debug)]
+struct PruneArgs {
+    /// Only remove entries cached more than this many days ago.
+    ///
+    /// Every cached entry is removed when omitted.
+    #[arg(long, value_name = "DAYS")]
+    max_age_days: Option<u64>,
+
+    /// Prune the proving and verifying key cache instead of the proof cache.
+    #[arg(long)]
+    keys: bool,
+
+    /// Directory of the proof cache, used unless `--keys` is given.
+    #[arg(long, value_name = "DIR", env = "SP1_PROOF_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Directory of the key cache, used with `--keys`.
+    #[arg(long, value_name = "DIR", env = "SP1_KEY_CACHE_DIR")]
+    key_cache_dir: Option<PathBuf>,
+}
+
+/// Options controlling the proof and key caches.
+///
+/// Proofs are cached by ELF, stdin, proof system and SP1 version, so running the
+/// same command twice only proves once. Proving and verifying keys are cached by
+/// ELF and SP1 version, so the setup only runs once per program.
+#[derive(Args, Debug)]
+struct CacheArgs {
+    /// Always generate a new proof, without reading or writing the proof cache.
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Directory of the proof cache.
+    ///
+    /// Defaults to `target/proof-cache` in the repository checkout this binary
+    /// was built from.
+    #[arg(long, value_name = "DIR", env = "SP1_PROOF_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+
+    #[command(flatten)]
+    keys: KeyCacheArgs,
+}
+
+/// Options controlling the proving and verifying key cache.
+#[derive(Args, Debug)]
+struct KeyCacheArgs {
+    /// Always run the program setup, without reading or writing the key cache.
+    #[arg(long)]
+    no_key_cache: bool,
+
+    /// Directory of the proving and verifying key cache.
+    ///
+    /// Defaults to `target/key-cache` in the repository checkout this binary
+    /// was built from.
+    #[arg(long, value_name = "DIR", env = "SP1_KEY_CACHE_DIR")]
+    key_cache_dir: Option<PathBuf>,
+}
+
+impl CacheArgs {
+    /// The proof cache to use, unless disabled with `--no-cache`.
+    fn cache(&self) -> Option<ProofCache> {
+        if self.no_cache {
+            return None;
+        }
+        Some(
+            self.cache_dir
+                .clone()
+                .map_or_else(ProofCache::default, ProofCache::new),
+        )
+    }
+
+    /// The key cache to use, unless disabled with `--no-key-cache`.
+    fn key_cache(&self) -> Option<KeyCache> {
+        self.keys.key_cache()
+    }
+}
+
+impl KeyCacheArgs {
+    /// The key cache to use, unless disabled with `--no-key-cache`.
+    fn key_cache(&self) -> Option<KeyCache> {
+        if self.no_key_cache {
+            return None;
+        }
+        Some(
+            self.key_cache_dir
+                .clone()
+                .map_or_else(KeyCache::default, KeyCache::new),
+        )
+    }
+}
+
+/// Options limiting the cost of a run.
+///
+/// Before a Groth16 or PLONK proof, the program is executed once and proving is
+/// refused if it runs for more than `--max-prove-cycles` cycles, unless `--force`
+/// is given.
+#[derive(Args, Debug)]
+struct LimitArgs {
+    /// Abort execution and proving after this many cycles.
+    #[arg(long, value_name = "CYCLES")]
+    max_cycles: Option<u64>,
+
+    /// Largest cycle count proven as a SNARK without `--force`.
+    #[arg(
+        long,
+        value_name = "CYCLES",
+        env = "SP1_MAX_PROVE_CYCLES",
+        default_value_t = DEFAULT_MAX_PROVE_CYCLES
+    )]
+    max_prove_cycles: u64,
+
+    /// Prove even if the program runs for more than `--max-prove-cycles` cycles.
+    #[arg(long)]
+    force: bool,
+}
+
+impl LimitArgs {
+    /// Execute an input before proving it as `system`.
+    ///
+    /// SNARK pre-flights are aborted just above `--max-prove-cycles` unless
+    /// `--force` is given, so a refused input never runs to completion. Accepted
+    /// ones print their [`ProvingEstimate`].
+    fn preflight(
+        &self,
+        client: &EnvProver,
+        program: &Program,
+        stdin: &SP1Stdin,
+        system: ProofSystem,
+    ) -> Result<(SP1PublicValues, ExecutionReport)> {
+        if !system.is_onchain_verifiable() {
+            return execute(client, program, stdin, self.max_cycles);
+        }
+        let execution = execute_within_budget(
+            client,
+            program,
+            stdin,
+            self.max_cycles,
+            self.max_prove_cycles,
+            self.force,
+        )?;
+
+        let estimate = ProvingEstimate::new(execution.1.total_instruction_count());
+        status!(
+            "📏 Pre-flight: {} cycles in {} shard(s)",
+            estimate.cycles,
+            estimate.shards
+        );
+        if estimate.cycles > self.max_prove_cycles && self.force {
+            status!(
+                "⚠️  Above --max-prove-cycles ({}), proving anyway (--force)",
+                self.max_prove_cycles
+            );
+        }
+        Ok(execution)
+    }
+}
+
+/// Options for generating a new proof.
+#[derive(Args, Debug)]
+struct ProveArgs {
     /// The input number for Fibonacci computation.
     ///
     /// Specifies which Fibonacci number to compute and prove.
@@ -71,54 +389,290 @@ struct StarknetArgs {
     #[arg(long, default_value = "3")]
     n: u32,
 
+    /// A JSON or TOML file holding the program input, e.g. `{ "n": 10 }`.
+    ///
+    /// The file is parsed as a `fibonacci_lib::FibonacciInput` and written to
+    /// the program stdin in place of `--n`.
+    #[arg(long, value_name = "FILE", conflicts_with = "n")]
+    input: Option<PathBuf>,
+
+    /// A file written to the program stdin as raw bytes, in place of `--n`.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["n", "input"])]
+    input_bytes: Option<PathBuf>,
+
     /// The proof system to use for generating the proof.
     ///
-    /// Currently supports Groth16, which is optimized for on-chain verification
-    /// due to its constant proof size and fast verification time.
+    /// Groth16 is optimized for on-chain verification due to its constant proof
+    /// size and fast verification time, and is the only system Garaga can verify
+    /// on Starknet. PLONK proofs produce a fixture and then fail with a
+    /// "not supported on Starknet" error. `core` and `compressed` skip the SNARK
+    /// wrapping for fast local iteration and produce no calldata.
     #[arg(long, value_enum, default_value = "groth16")]
     system: ProofSystem,
+
+    /// Skip proving and write a mock fixture with dummy calldata.
+    ///
+    /// The program is only executed to obtain its public values. The fixture is
+    /// marked `"mock": true` and its calldata has the layout of a real proof, but
+    /// is rejected on-chain. Also enabled by `SP1_PROVER=mock`.
+    #[arg(long)]
+    mock: bool,
+
+    /// Path of an SP1 program ELF to prove instead of the embedded Fibonacci program.
+    #[arg(long, value_name = "FILE")]
+    elf: Option<PathBuf>,
+
+    #[command(flatten)]
+    cache: CacheArgs,
+
+    #[command(flatten)]
+    limits: LimitArgs,
+
+    #[command(flatten)]
+    output: OutputArgs,
+
+    #[command(flatten)]
+    report: ReportArgs,
 }
 
-/// Supported proof systems for Starknet verification.
+/// Options for converting an existing proof.
+#[derive(Args, Debug)]
+#[command(group(ArgGroup::new("source").required(true).args(["proof", "fixture"])))]
+struct ConvertArgs {
+    /// A proof saved with `SP1ProofWithPublicValues::save` (bincode).
+    #[arg(long, value_name = "FILE")]
+    proof: Option<PathBuf>,
+
+    /// The verification key of the saved proof (`vk.bin`).
+    ///
+    /// Derived from the program (`--elf`, or the embedded Fibonacci program)
+    /// when omitted.
+    #[arg(long, value_name = "FILE", requires = "proof")]
+    vk: Option<PathBuf>,
+
+    /// Path of the SP1 program ELF the saved proof was generated for.
+    #[arg(long, value_name = "FILE", requires = "proof", conflicts_with = "vk")]
+    elf: Option<PathBuf>,
+
+    /// A JSON fixture previously written by this binary, e.g. `groth16-fixture.json`.
+    #[arg(long, value_name = "FILE")]
+    fixture: Option<PathBuf>,
+
+    /// Wrap a saved compressed proof into the given proof system before converting.
+    ///
+    /// Wrapping always runs on the local CPU prover, whatever `SP1_PROVER` selects.
+    #[arg(long, value_enum, value_name = "SYSTEM", requires = "proof")]
+    wrap: Option<WrapTarget>,
+
+    /// The proof system of the saved fixture.
+    ///
+    /// Only used with `--fixture`; the system of a `--proof` file is detected
+    /// from the proof itself.
+    #[arg(long, value_enum, default_value = "groth16")]
+    system: ProofSystem,
+
+    #[command(flatten)]
+    keys: KeyCacheArgs,
+
+    #[command(flatten)]
+    output: OutputArgs,
+
+    #[command(flatten)]
+    report: ReportArgs,
+}
+
+/// Options for proving a batch of inputs.
 ///
-/// Each proof system has different characteristics:
-/// - **Groth16**: Constant-size proofs, fast verification, requires trusted setup
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
-enum ProofSystem {
-    /// Groth16 zero-knowledge proof system.
+/// Fixture files are named `<NAME>-<INDEX>` and written, with `manifest.json`, to
+/// the `batch` subdirectory of the default fixture directory unless `--out-dir`
+/// is given. With `--stdout`, the manifest is printed instead of the fixtures.
+#[derive(Args, Debug)]
+#[command(group(ArgGroup::new("inputs").required(true).multiple(true).args(["n", "input"])))]
+struct BatchArgs {
+    /// Fibonacci indices to prove, e.g. `0,1,47` or `0..=10`.
     ///
-    /// Groth16 is currently the most practical choice for on-chain verification because:
-    /// - Constant proof size (~200 bytes)
-    /// - Fast verification time
-    /// - Well-supported by Garaga library
-    /// - Efficient gas costs on Starknet
+    /// Accepts comma-separated values and ranges (`A..B` or `A..=B`), and can be
+    /// repeated.
+    #[arg(long, value_name = "N|RANGE", value_delimiter = ',')]
+    n: Vec<InputRange>,
+
+    /// JSON or TOML input files to prove after the `--n` values.
+    #[arg(long, value_name = "FILE")]
+    input: Vec<PathBuf>,
+
+    /// The proof system to use for every input.
+    #[arg(long, value_enum, default_value = "groth16")]
+    system: ProofSystem,
+
+    /// Skip proving and write mock fixtures with dummy calldata.
+    #[arg(long)]
+    mock: bool,
+
+    /// Path of an SP1 program ELF to prove instead of the embedded Fibonacci program.
+    #[arg(long, value_name = "FILE")]
+    elf: Option<PathBuf>,
+
+    #[command(flatten)]
+    cache: CacheArgs,
+
+    #[command(flatten)]
+    limits: LimitArgs,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// Options controlling the machine-readable run report.
+#[derive(Args, Debug)]
+struct ReportArgs {
+    /// Write a JSON report of the run (input, public values, cycles, stage
+    /// durations, proof size, calldata length and output files) to this file.
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+
+    /// Print the JSON report of the run to stdout.
+    ///
+    /// Progress messages are sent to stderr so that stdout can be piped.
+    #[arg(long, conflicts_with = "stdout")]
+    json: bool,
+}
+
+impl ReportArgs {
+    /// Whether a report was requested.
+    fn enabled(&self) -> bool {
+        self.report.is_some() || self.json
+    }
+
+    /// Write and print the report as requested.
+    fn emit(&self, report: &RunReport) -> Result<()> {
+        if let Some(path) = &self.report {
+            report.write(path)?;
+            status!("📝 Run report saved to: {}", path.display());
+        }
+        if self.json {
+            println!("{}", report.to_json()?);
+        }
+        Ok(())
+    }
+}
+
+/// Proof systems a compressed proof can be wrapped into.
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+enum WrapTarget {
+    /// Groth16, the proof system verified by Garaga on Starknet.
     Groth16,
 }
 
-/// Test fixture containing SP1 proof data for contract testing.
-///
-/// This structure contains all the necessary data to test SP1 proof verification
-/// in Cairo contracts. It includes both the raw proof data and metadata.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-struct SP1FibonacciProofFixture {
-    /// The verification key for the SP1 program (hex string).
+/// Options for verifying a saved proof.
+#[derive(Args, Debug)]
+struct VerifyArgs {
+    /// A proof saved with `SP1ProofWithPublicValues::save`, e.g. `groth16-proof.bin`.
+    #[arg(long, value_name = "FILE")]
+    proof: PathBuf,
+
+    /// The verification key to check the proof against.
+    ///
+    /// Defaults to the `vk.bin` file next to the proof.
+    #[arg(long, value_name = "FILE")]
+    vk: Option<PathBuf>,
+}
+
+/// Options for decoding a calldata file.
+#[derive(Args, Debug)]
+struct DecodeCalldataArgs {
+    /// A calldata file written with the `hex` encoding, e.g. `groth16-calldata.txt`.
+    #[arg(long, value_name = "FILE")]
+    calldata: PathBuf,
+
+    /// The fixture to check the calldata against.
+    ///
+    /// Defaults to the companion `<NAME>-fixture.json` of a `<NAME>-calldata.txt`
+    /// file, when it exists.
+    #[arg(long, value_name = "FILE")]
+    fixture: Option<PathBuf>,
+}
+
+impl DecodeCalldataArgs {
+    /// The fixture to check against, if any.
+    fn fixture_path(&self) -> Option<PathBuf> {
+        if let Some(path) = &self.fixture {
+            return Some(path.clone());
+        }
+
+        let file_name = self.calldata.file_name()?.to_str()?;
+        let name = file_name.strip_suffix("-calldata.txt")?;
+        let companion = self.calldata.with_file_name(format!("{name}-fixture.json"));
+        companion.exists().then_some(companion)
+    }
+}
+
+/// Report printed by `decode-calldata`.
+#[derive(Serialize)]
+struct DecodeReport {
+    #[serde(flatten)]
+    calldata: DecodedCalldata,
+    /// Section-by-section comparison with the fixture, when one was found.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checks: Option<Vec<SectionCheck>>,
+}
+
+/// Options controlling the fixture and calldata output.
+#[derive(Args, Debug)]
+struct OutputArgs {
+    /// The numeric representation of the generated calldata.
     ///
-    /// This key uniquely identifies the SP1 program and must match
-    /// the `SP1_PROGRAM` constant in the verification contract.
-    vkey: String,
+    /// `felt` range-checks every element against the felt252 modulus and fails
+    /// instead of writing calldata that cannot be represented on Starknet.
+    #[arg(long, value_enum, default_value = "biguint")]
+    calldata_format: CalldataFormat,
 
-    /// The public values committed by the SP1 program (hex string).
+    /// The file format of the generated calldata.
     ///
-    /// These are the publicly verifiable outputs of the computation,
-    /// encoded in a format suitable for on-chain verification.
-    public_values: String,
+    /// `hex` is loaded by the Cairo tests; `json`, `starkli`, `sncast` and
+    /// `binary` target deployment tooling and backend services.
+    #[arg(long, value_enum, default_value = "hex")]
+    calldata_encoding: CalldataEncoding,
 
-    /// The complete proof data (hex string).
+    /// Directory the fixture files are written to.
     ///
-    /// This contains the cryptographic proof that can be verified
-    /// to confirm the computation was performed correctly.
-    proof: String,
+    /// Defaults to the `contracts/src/fixtures` directory of the repository
+    /// checkout this binary was built from.
+    #[arg(long, value_name = "DIR")]
+    out_dir: Option<PathBuf>,
+
+    /// Base name of the fixture files (`<NAME>-fixture.json`, `<NAME>-calldata.txt`).
+    ///
+    /// Defaults to the proof system name, e.g. `groth16`.
+    #[arg(long, value_name = "NAME")]
+    fixture_name: Option<String>,
+
+    /// Do not write any fixture files to disk.
+    #[arg(long, conflicts_with_all = ["out_dir", "fixture_name"])]
+    no_fixture: bool,
+
+    /// Print the fixture JSON to stdout.
+    ///
+    /// Progress messages are sent to stderr so that stdout can be piped.
+    #[arg(long)]
+    stdout: bool,
+}
+
+impl OutputArgs {
+    /// The library options matching these arguments.
+    fn fixture_options(&self) -> FixtureOptions {
+        let out_dir = if self.no_fixture {
+            None
+        } else {
+            Some(self.out_dir.clone().unwrap_or_else(default_fixture_dir))
+        };
+
+        FixtureOptions {
+            out_dir,
+            name: self.fixture_name.clone(),
+            format: self.calldata_format,
+            encoding: self.calldata_encoding,
+        }
+    }
 }
 
 /// Main entry point for Starknet proof generation.
@@ -135,226 +689,668 @@ struct SP1FibonacciProofFixture {
 /// 2. **Proving**: Generate the cryptographic proof using SP1
 /// 3. **Formatting**: Convert proof to Starknet-compatible format using Garaga
 /// 4. **Output**: Save fixtures for testing and integration
-fn main() {
-    // Initialize logging for detailed execution information
-    sp1_sdk::utils::setup_logger();
-
+///
+/// ## Exit Codes
+///
+/// Failures exit with the code of the pipeline stage that failed (see
+/// [`Error::exit_code`]), so CI scripts can react to each stage separately.
+fn main() -> ExitCode {
     // Parse command-line arguments
     let args = StarknetArgs::parse();
 
+    if args.prints_json() {
+        // Keep stdout reserved for JSON output. The SP1 logger writes to
+        // stdout, so it is left disabled in this mode.
+        fibonacci_starknet::console::redirect_to_stderr();
+    } else {
+        // Initialize logging for detailed execution information
+        sp1_sdk::utils::setup_logger();
+    }
+
+    let result = match args.command {
+        None => prove(args.prove),
+        Some(Command::Convert(convert_args)) => convert(convert_args),
+        Some(Command::Batch(batch_args)) => batch(batch_args),
+        Some(Command::Verify(verify_args)) => verify(verify_args),
+        Some(Command::DecodeCalldata(decode_args)) => decode_calldata(decode_args),
+        Some(Command::Cache(CacheCommand::Prune(prune_args))) => prune_cache(prune_args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("❌ Error: {error}");
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+/// Generate the proof and fixtures described by `args`.
+fn prove(args: ProveArgs) -> Result<()> {
     // Initialize the SP1 prover client
     // This client handles communication with the zkVM and proof generation
     let client = ProverClient::from_env();
 
+    // Load the program to prove, falling back to the embedded Fibonacci ELF
+    let program = Program::from_elf_arg(args.elf.as_deref())?;
+    if !program.is_embedded() {
+        status!("📂 Program: {} ({})", program.name, program.elf_sha256());
+    }
+
+    // Prepare program inputs
+    // The Fibonacci program expects a single u32 input representing the Fibonacci index
+    let input =
+        ProgramInput::from_args(args.n, args.input.as_deref(), args.input_bytes.as_deref())?;
+    let stdin = input.to_stdin();
+
+    let mut report = RunReport::new("prove");
+    report.set_program(&program);
+    report.input = Some(input.clone());
+    report.system = Some(args.system.name().to_string());
+
+    status!("🔢 Input: {}", input.describe());
+    status!("🔧 Proof System: {:?}", args.system);
+
+    // The SDK's mock prover returns proofs Garaga cannot parse, so handle both
+    // the flag and the environment variable with our own mock fixtures. They
+    // only need the verification key, so skip the proving key setup
+    if args.mock || mock_prover_requested() {
+        report.command = "mock".to_string();
+        let (vk, setup_ms) =
+            timed(|| derive_vk(&client, &program, args.cache.key_cache().as_ref()));
+        let vk = vk?;
+        report.durations.setup_ms = Some(setup_ms);
+        return prove_mock(&client, &program, &vk, &stdin, &args, report);
+    }
+
     // Set up the program for proving
     // This generates the proving and verification keys for the SP1 program
-    let (pk, vk) = client.setup(FIBONACCI_ELF);
+    let (keys, setup_ms) =
+        timed(|| setup_program(&client, &program, args.cache.key_cache().as_ref()));
+    let (pk, vk) = keys?;
+    report.durations.setup_ms = Some(setup_ms);
 
-    // Prepare program inputs
-    // The SP1 program expects a single u32 input representing the Fibonacci index
-    let mut stdin = SP1Stdin::new();
-    stdin.write(&args.n);
+    // Reuse the same proof if it is already cached
+    let cache = args.cache.cache();
+    let (cached_proof, key) =
+        load_cached_proof(&client, &program, &stdin, args.system, cache.as_ref())?;
 
-    println!("🔢 Input (n): {}", args.n);
-    println!("🔧 Proof System: {:?}", args.system);
-    println!("🚀 Generating proof...");
+    // Proving does not report cycles, so execute the program first for the
+    // pre-flight check of new SNARK proofs and for the report
+    let preflight = cached_proof.is_none() && args.system.is_onchain_verifiable();
+    if preflight || args.report.enabled() {
+        let (execution, execute_ms) = timed(|| {
+            if preflight {
+                args.limits
+                    .preflight(&client, &program, &stdin, args.system)
+            } else {
+                execute(&client, &program, &stdin, args.limits.max_cycles)
+            }
+        });
+        let (_, execution_report) = execution?;
+        report.set_execution(&execution_report);
+        report.durations.execute_ms = Some(execute_ms);
+    }
 
     // Generate the proof based on the selected proof system
-    let proof = match args.system {
-        ProofSystem::Groth16 => {
-            println!("   Using Groth16 proof system for Starknet compatibility");
-            client.prove(&pk, &stdin).groth16().run()
+    let (proof, prove_ms) = timed(|| match cached_proof {
+        Some(proof) => {
+            status!("♻️  Using cached proof (pass --no-cache to prove again)");
+            report.cached_proof = true;
+            Ok(proof)
+        }
+        None => {
+            status!("🚀 Generating proof...");
+            status!("   {}", proof_system_note(args.system));
+            let proof = generate_proof(&client, &pk, &stdin, args.system, args.limits.max_cycles)?;
+            status!("✅ Proof generated successfully!");
+            store_cached_proof(cache.as_ref(), key.as_ref(), &proof)?;
+            Ok::<_, Error>(proof)
         }
+    });
+    let proof = proof?;
+    report.durations.prove_ms = Some(prove_ms);
+    report.set_proof(&proof, args.system);
+
+    // Create test fixtures and calldata for Starknet integration
+    status!("📁 Creating test fixtures...");
+    let (output, convert_ms) =
+        timed(|| create_proof_fixture(&proof, &vk, args.system, &args.output.fixture_options()));
+    let output = output?;
+    report.durations.convert_ms = Some(convert_ms);
+    report.set_fixture(&output);
+
+    // Display key information about the proof
+    status!("📋 Proof Information:");
+    status!("  Verification Key: {}", output.fixture.vkey);
+    status!("  Public Values: {}", output.fixture.public_values);
+    if args.system.is_onchain_verifiable() {
+        status!("  Proof Size: {} bytes", proof.bytes().len());
     }
-    .expect("failed to generate proof");
 
-    println!("✅ Proof generated successfully!");
+    report_fixture(&output, &args.output)?;
+    args.report.emit(&report)?;
+    require_starknet_calldata(args.system, &output)
+}
+
+/// Prove every input of a batch with a single setup.
+fn batch(args: BatchArgs) -> Result<()> {
+    let client = ProverClient::from_env();
 
-    // Create test fixtures and calldata for Starknet integration
-    create_proof_fixture(&proof, &vk, args.system);
+    let program = Program::from_elf_arg(args.elf.as_deref())?;
+    if !program.is_embedded() {
+        status!("📂 Program: {} ({})", program.name, program.elf_sha256());
+    }
+
+    // Collect the inputs before the setup, so that invalid files fail fast
+    let mut inputs: Vec<FibonacciInput> = args
+        .n
+        .iter()
+        .flat_map(|range| range.values())
+        .map(|n| FibonacciInput { n })
+        .collect();
+    for path in &args.input {
+        inputs.push(read_input_file(path)?);
+    }
+
+    let mock = args.mock || mock_prover_requested();
+    status!(
+        "📦 Batch of {} inputs, {} proof system{}",
+        inputs.len(),
+        args.system.name(),
+        if mock { " (mock)" } else { "" }
+    );
+
+    // Set up the program once and reuse the keys for every input. Mock fixtures
+    // only need the verification key, so skip the proving key setup
+    let key_cache = args.cache.key_cache();
+    let (pk, vk) = if mock {
+        (None, derive_vk(&client, &program, key_cache.as_ref())?)
+    } else {
+        let (pk, vk) = setup_program(&client, &program, key_cache.as_ref())?;
+        (Some(pk), vk)
+    };
+
+    let mut options = args.output.fixture_options();
+    if args.output.out_dir.is_none() && options.out_dir.is_some() {
+        options.out_dir = Some(default_batch_dir());
+    }
+    let base_name = options.file_name(args.system);
+
+    let cache = args.cache.cache();
+    let mut manifest = BatchManifest::new(&program, vk.bytes32(), args.system, mock);
+    for (index, input) in inputs.into_iter().enumerate() {
+        let name = batch_fixture_name(&base_name, index);
+        status!("🔢 [{}] {}: n = {}", index, name, input.n);
+
+        let stdin = ProgramInput::Fibonacci(input).to_stdin();
+        let (cached_proof, key) = if mock {
+            (None, None)
+        } else {
+            load_cached_proof(&client, &program, &stdin, args.system, cache.as_ref())?
+        };
+        let cached = cached_proof.is_some();
+        let (public_values, report) = if mock || cached {
+            execute(&client, &program, &stdin, args.limits.max_cycles)?
+        } else {
+            args.limits
+                .preflight(&client, &program, &stdin, args.system)?
+        };
+        let cycles = report.total_instruction_count();
+
+        let entry_options = FixtureOptions {
+            name: Some(name.clone()),
+            ..options.clone()
+        };
+        // Mock batches are the ones without a proving key
+        let (output, proof_time) = match &pk {
+            None => {
+                let output = create_mock_fixture(
+                    &vk,
+                    public_values.as_slice(),
+                    args.system,
+                    &entry_options,
+                )?;
+                (output, None)
+            }
+            Some(pk) => match cached_proof {
+                Some(proof) => {
+                    let output = create_proof_fixture(&proof, &vk, args.system, &entry_options)?;
+                    (output, None)
+                }
+                None => {
+                    let start = Instant::now();
+                    let proof =
+                        generate_proof(&client, pk, &stdin, args.system, args.limits.max_cycles)?;
+                    let proof_time = start.elapsed();
+                    store_cached_proof(cache.as_ref(), key.as_ref(), &proof)?;
+                    let output = create_proof_fixture(&proof, &vk, args.system, &entry_options)?;
+                    (output, Some(proof_time))
+                }
+            },
+        };
+
+        match proof_time {
+            Some(time) => status!("   ✅ {} cycles, proved in {:.1?}", cycles, time),
+            None if cached => status!("   ♻️  {} cycles, cached proof", cycles),
+            None => status!("   ✅ {} cycles", cycles),
+        }
+        let mut entry = BatchEntry::new(index, input, name, &output, cycles, proof_time);
+        entry.cached = cached;
+        manifest.entries.push(entry);
+    }
+
+    match &options.out_dir {
+        Some(out_dir) => {
+            let path = manifest.write(out_dir)?;
+            status!("💾 Fixtures saved to: {}", out_dir.display());
+            status!("   📄 {}", path.display());
+        }
+        None => status!("💡 Fixture files were not written (--no-fixture)"),
+    }
+    if args.output.stdout {
+        println!("{}", serde_json::to_string_pretty(&manifest)?);
+    }
+
+    if args.system.is_onchain_verifiable() && !args.system.has_starknet_calldata() {
+        return Err(Error::UnsupportedOnStarknet(args.system.name()));
+    }
+    Ok(())
 }
 
-/// Convert SP1 proof to Starknet-compatible calldata using Garaga.
-///
-/// This function transforms an SP1 proof into the format expected by the
-/// Garaga SP1 Verifier on Starknet. The conversion process:
-///
-/// 1. Extracts the verification key from the SP1 proof
-/// 2. Creates a Garaga-compatible Groth16 proof structure
-/// 3. Generates calldata formatted for Starknet contract calls
-///
-/// ## Parameters
-///
-/// - `proof`: The SP1 proof with public values
-/// - `vk`: The SP1 verification key
-///
-/// ## Returns
-///
-/// A vector of `BigUint` values representing the calldata that can be
-/// passed to the Starknet verification contract.
-///
-/// ## Garaga Integration
+/// Whether `SP1_PROVER=mock` asks for mock fixtures.
+fn mock_prover_requested() -> bool {
+    std::env::var("SP1_PROVER").is_ok_and(|prover| prover == "mock")
+}
+
+/// Generate a proof of the given system, aborting after `cycle_limit` cycles if set.
+fn generate_proof(
+    client: &EnvProver,
+    pk: &SP1ProvingKey,
+    stdin: &SP1Stdin,
+    system: ProofSystem,
+    cycle_limit: Option<u64>,
+) -> Result<SP1ProofWithPublicValues> {
+    let mut builder = client.prove(pk, stdin);
+    if let Some(cycle_limit) = cycle_limit {
+        builder = builder.cycle_limit(cycle_limit);
+    }
+    match system {
+        ProofSystem::Groth16 => builder.groth16().run(),
+        ProofSystem::Plonk => builder.plonk().run(),
+        ProofSystem::Core => builder.core().run(),
+        ProofSystem::Compressed => builder.compressed().run(),
+    }
+    .map_err(Error::Prover)
+}
+
+/// Look up the proof of `program` on `stdin` in the cache, if enabled.
 ///
-/// This function uses the Garaga library to:
-/// - Convert SP1 proof format to Groth16 format
-/// - Generate BN254 curve-compatible calldata
-/// - Ensure compatibility with the on-chain verifier
-pub fn get_sp1_garaga_starknet_calldata(
+/// Also returns the cache key, to store the proof under once it is generated.
+fn load_cached_proof(
+    client: &EnvProver,
+    program: &Program,
+    stdin: &SP1Stdin,
+    system: ProofSystem,
+    cache: Option<&ProofCache>,
+) -> Result<(Option<SP1ProofWithPublicValues>, Option<ProofCacheKey>)> {
+    let Some(cache) = cache else {
+        return Ok((None, None));
+    };
+    let key = ProofCacheKey::new(program, stdin, system, client.version())?;
+    Ok((cache.load(&key), Some(key)))
+}
+
+/// Store a newly generated proof in the cache, if enabled.
+fn store_cached_proof(
+    cache: Option<&ProofCache>,
+    key: Option<&ProofCacheKey>,
     proof: &SP1ProofWithPublicValues,
+) -> Result<()> {
+    if let (Some(cache), Some(key)) = (cache, key) {
+        let path = cache.store(key, proof)?;
+        status!("💾 Cached proof: {}", path.display());
+    }
+    Ok(())
+}
+
+/// Remove cached proofs.
+fn prune_cache(args: PruneArgs) -> Result<()> {
+    let max_age = args
+        .max_age_days
+        .map(|days| Duration::from_secs(days * 24 * 60 * 60));
+
+    let stats = if args.keys {
+        let cache = args
+            .key_cache_dir
+            .map_or_else(KeyCache::default, KeyCache::new);
+        status!("🧹 Pruning key cache {}", cache.dir().display());
+        cache.prune(max_age)?
+    } else {
+        let cache = args
+            .cache_dir
+            .map_or_else(ProofCache::default, ProofCache::new);
+        status!("🧹 Pruning proof cache {}", cache.dir().display());
+        cache.prune(max_age)?
+    };
+
+    status!(
+        "✅ Removed {} cached files ({} bytes), kept {}",
+        stats.removed,
+        stats.freed_bytes,
+        stats.kept
+    );
+    Ok(())
+}
+
+/// Set up the program, reusing the cached keys of a previous run if enabled.
+fn setup_program(
+    client: &EnvProver,
+    program: &Program,
+    key_cache: Option<&KeyCache>,
+) -> Result<(SP1ProvingKey, SP1VerifyingKey)> {
+    match key_cache {
+        Some(cache) => cache.setup(program, client.version(), |elf| {
+            status!(
+                "🔧 Setting up {} (keys are cached for later runs)...",
+                program.name
+            );
+            client.setup(elf)
+        }),
+        None => Ok(client.setup(&program.elf)),
+    }
+}
+
+/// Describe what a proof system is used for, when proving starts.
+fn proof_system_note(system: ProofSystem) -> &'static str {
+    match system {
+        ProofSystem::Groth16 => "Using Groth16 proof system for Starknet compatibility",
+        ProofSystem::Plonk => "Using PLONK proof system (not verifiable on Starknet)",
+        ProofSystem::Core => "Using core proof system (not verifiable on-chain)",
+        ProofSystem::Compressed => "Using compressed proof system (not verifiable on-chain)",
+    }
+}
+
+/// Execute the program and write mock fixtures instead of proving.
+fn prove_mock(
+    client: &EnvProver,
+    program: &Program,
     vk: &SP1VerifyingKey,
-) -> Vec<BigUint> {
-    // Get the SP1 Groth16 verification key from Garaga
-    // This is the universal verification key for SP1 Groth16 proofs
-    let sp1_groth16_vk = get_sp1_vk();
-
-    // Extract the program verification key as bytes
-    // This identifies the specific SP1 program being proven
-    let vkey_bytes: Vec<u8> = hex::decode(&vk.bytes32()[2..]).unwrap();
-
-    // Create a Garaga-compatible Groth16 proof from the SP1 proof
-    // This conversion handles the format differences between SP1 and Garaga
-    let groth16_proof =
-        Groth16Proof::from_sp1(vkey_bytes, proof.public_values.to_vec(), proof.bytes());
-
-    // Generate Starknet calldata for the proof
-    // This creates the properly formatted data for contract calls
-    /*
-     Note: You can use garaga::calldata::full_proof_with_hints::groth16::get_groth16_calldata_felt
-     instead to output the result in Vec<Felt> type, for better backend integration with tools like
-     https://github.com/xJonathanLEI/starkli
-    */
-    get_groth16_calldata(&groth16_proof, &sp1_groth16_vk, CurveID::BN254).unwrap()
-}
-
-/// Convert a vector of BigUint values to hexadecimal string format.
-///
-/// This function formats calldata for easy storage and loading in test fixtures.
-/// Each BigUint value is converted to a hexadecimal string and placed on its own line.
-///
-/// ## Parameters
-///
-/// - `calldata`: A vector of BigUint values representing the proof calldata
-///
-/// ## Returns
-///
-/// A string where each line contains a hexadecimal representation of a BigUint value,
-/// suitable for saving to a text file and loading in Cairo tests.
-///
-/// ## Format
-///
-/// The output format is compatible with Starknet Foundry's `read_txt` function:
-/// ```
-/// 0xff
-/// 0xfff
-/// 0x1234
-/// ```
-///
-/// ## Example
-///
-/// ```rust
-/// let calldata = vec![BigUint::from(255u32), BigUint::from(4095u32)];
-/// let hex_string = biguint_vec_to_hex_string(&calldata);
-/// // Result: "0xff\n0xfff\n"
-/// ```
-pub fn biguint_vec_to_hex_string(calldata: Vec<BigUint>) -> String {
-    calldata
-        .iter()
-        .map(|big_uint| format!("0x{:x}", big_uint))
-        .collect::<Vec<String>>()
-        .join("\n")
-        .to_string()
-        + "\n" // Add final newline for proper file formatting
+    stdin: &SP1Stdin,
+    args: &ProveArgs,
+    mut report: RunReport,
+) -> Result<()> {
+    status!("🎭 Mock mode: executing the program without proving...");
+    let (execution, execute_ms) = timed(|| execute(client, program, stdin, args.limits.max_cycles));
+    let (public_values, execution_report) = execution?;
+    report.set_execution(&execution_report);
+    report.durations.execute_ms = Some(execute_ms);
+
+    status!("📁 Creating mock test fixtures...");
+    let (output, convert_ms) = timed(|| {
+        create_mock_fixture(
+            vk,
+            public_values.as_slice(),
+            args.system,
+            &args.output.fixture_options(),
+        )
+    });
+    let output = output?;
+    report.durations.convert_ms = Some(convert_ms);
+    report.set_fixture(&output);
+
+    status!("📋 Proof Information:");
+    status!("  Verification Key: {}", output.fixture.vkey);
+    status!("  Public Values: {}", output.fixture.public_values);
+    status!("⚠️  Mock fixture: the calldata will be rejected by the on-chain verifier");
+
+    report_fixture(&output, &args.output)?;
+    args.report.emit(&report)?;
+    require_starknet_calldata(args.system, &output)
 }
 
-/// Create comprehensive test fixtures for the generated proof.
-///
-/// This function generates all the necessary files for testing SP1 proof verification
-/// in Cairo contracts. It creates both human-readable JSON fixtures and
-/// machine-readable calldata files.
-///
-/// ## Generated Files
-///
-/// 1. **JSON Fixture** (`{system}-fixture.json`):
-///    - Complete proof metadata
-///    - Verification key
-///    - Public values
-///    - Raw proof data
-///
-/// 2. **Calldata File** (`{system}-calldata.txt`):
-///    - Formatted calldata for Starknet contracts
-///    - Compatible with Starknet Foundry test framework
-///    - Ready for direct use in contract calls
-///
-/// ## Parameters
-///
-/// - `proof`: The generated SP1 proof with public values
-/// - `vk`: The SP1 verification key
-/// - `system`: The proof system used (affects file naming)
-///
-/// ## Output Location
+/// Regenerate fixtures and calldata from a saved proof or fixture.
+fn convert(args: ConvertArgs) -> Result<()> {
+    let options = args.output.fixture_options();
+    let mut report = RunReport::new("convert");
+
+    let (output, system) = if let Some(path) = &args.proof {
+        status!("📂 Loading proof from {}", path.display());
+        let mut proof = load_proof(path)?;
+
+        // Wrapping only runs on the local prover, whatever SP1_PROVER selects
+        if let Some(WrapTarget::Groth16) = args.wrap {
+            status!("🌯 Wrapping compressed proof into Groth16 (local prover)...");
+            let prover = ProverClient::builder().cpu().build();
+            let (wrapped, prove_ms) = timed(|| wrap_groth16(&prover, proof));
+            proof = wrapped?;
+            report.durations.prove_ms = Some(prove_ms);
+        }
+        let system = ProofSystem::of_proof(&proof.proof);
+        report.set_proof(&proof, system);
+
+        // The saved proof does not carry its verification key, so load it or
+        // derive it from the program the proof was generated for
+        let vk = match &args.vk {
+            Some(vk_path) => load_vk(vk_path)?,
+            None => {
+                let program = Program::from_elf_arg(args.elf.as_deref())?;
+                report.set_program(&program);
+                let client = ProverClient::from_env();
+                let key_cache = args.keys.key_cache();
+                let (vk, setup_ms) = timed(|| derive_vk(&client, &program, key_cache.as_ref()));
+                report.durations.setup_ms = Some(setup_ms);
+                vk?
+            }
+        };
+
+        status!("🔄 Converting proof to Starknet calldata...");
+        let (output, convert_ms) = timed(|| create_proof_fixture(&proof, &vk, system, &options));
+        report.durations.convert_ms = Some(convert_ms);
+        (output?, system)
+    } else {
+        let path = args
+            .fixture
+            .as_ref()
+            .expect("clap requires --proof or --fixture");
+        status!("📂 Loading fixture from {}", path.display());
+        let fixture = SP1FibonacciProofFixture::load(path)?;
+        report.system = Some(args.system.name().to_string());
+
+        status!("🔄 Converting fixture to Starknet calldata...");
+        let (output, convert_ms) = timed(|| convert_fixture(fixture, args.system, &options));
+        report.durations.convert_ms = Some(convert_ms);
+        (output?, args.system)
+    };
+    report.set_fixture(&output);
+
+    status!("📋 Proof Information:");
+    status!("  Verification Key: {}", output.fixture.vkey);
+    status!("  Public Values: {}", output.fixture.public_values);
+
+    report_fixture(&output, &args.output)?;
+    args.report.emit(&report)?;
+    require_starknet_calldata(system, &output)
+}
+
+/// Verify a saved proof against its verification key, without any network prover.
+fn verify(args: VerifyArgs) -> Result<()> {
+    status!("📂 Loading proof from {}", args.proof.display());
+    let proof = load_proof(&args.proof)?;
+
+    let vk_path = args
+        .vk
+        .unwrap_or_else(|| args.proof.with_file_name(VK_FILE_NAME));
+    status!("🔑 Loading verification key from {}", vk_path.display());
+    let vk = load_vk(&vk_path)?;
+
+    status!("🔍 Verifying proof...");
+    ProverClient::builder().cpu().build().verify(&proof, &vk)?;
+
+    status!("✅ Successfully verified proof!");
+    status!("  Verification Key: {}", vk.bytes32());
+    status!(
+        "  Public Values: 0x{}",
+        hex::encode(proof.public_values.as_slice())
+    );
+
+    Ok(())
+}
+
+/// Print the sections of a calldata file and compare them with its fixture.
+fn decode_calldata(args: DecodeCalldataArgs) -> Result<()> {
+    status!("📂 Loading calldata from {}", args.calldata.display());
+    let text = std::fs::read_to_string(&args.calldata).map_err(|source| Error::FixtureIo {
+        path: args.calldata.clone(),
+        source,
+    })?;
+    let calldata = decode_groth16_calldata(&parse_hex_calldata(&text)?)?;
+    status!(
+        "✅ Decoded {} public inputs and {} hint felts",
+        calldata.public_inputs.len(),
+        calldata.hints.len
+    );
+    for (title, section) in [
+        ("Pairing check hints", &calldata.hints.mpcheck),
+        ("MSM hints", &calldata.hints.msm),
+    ] {
+        status!(
+            "   {}: {} felts at offset {}",
+            title,
+            section.len,
+            section.offset
+        );
+        for block in &section.blocks {
+            match block.items {
+                Some(items) => status!(
+                    "     {:<20} {:>5} felts at {:>5} ({} items)",
+                    block.name,
+                    block.len,
+                    block.offset,
+                    items
+                ),
+                None => status!(
+                    "     {:<20} {:>5} felts at {:>5}",
+                    block.name,
+                    block.len,
+                    block.offset
+                ),
+            }
+        }
+    }
+
+    let checks = match args.fixture_path() {
+        Some(path) => {
+            status!("🔍 Checking against fixture {}", path.display());
+            let fixture = SP1FibonacciProofFixture::load(&path)?;
+            Some(calldata.check_fixture(&fixture)?)
+        }
+        None => {
+            status!("💡 No fixture found, skipping consistency checks (use --fixture)");
+            None
+        }
+    };
+
+    let mismatch = checks.iter().flatten().find(|check| !check.ok).cloned();
+
+    let report = DecodeReport { calldata, checks };
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    match mismatch {
+        Some(check) => Err(Error::CalldataMismatch {
+            section: check.section,
+            expected: check.expected,
+            actual: check.actual,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Derive the verification key of a program, through the key cache if enabled.
+fn derive_vk(
+    client: &EnvProver,
+    program: &Program,
+    key_cache: Option<&KeyCache>,
+) -> Result<SP1VerifyingKey> {
+    let setup = |elf: &[u8]| {
+        status!("🔑 Deriving verification key of {}...", program.name);
+        client.setup(elf)
+    };
+    match key_cache {
+        Some(cache) => cache.vk(program, client.version(), setup),
+        None => Ok(setup(&program.elf).1),
+    }
+}
+
+/// Fail with an explicit error for SNARKs that Starknet cannot verify.
 ///
-/// Files are saved to `../contracts/src/fixtures/` relative to the script directory.
-fn create_proof_fixture(
-    proof: &SP1ProofWithPublicValues,
-    vk: &SP1VerifyingKey,
-    system: ProofSystem,
-) {
-    println!("📁 Creating test fixtures...");
+/// Called once the fixture has been written, so the proof is not lost. Core and
+/// compressed proofs are expected to lack calldata and only get a hint.
+fn require_starknet_calldata(system: ProofSystem, output: &FixtureOutput) -> Result<()> {
+    if system.has_starknet_calldata() {
+        return Ok(());
+    }
+    if system.is_onchain_verifiable() {
+        return Err(Error::UnsupportedOnStarknet(system.name()));
+    }
+
+    status!("💡 {} proofs are not verifiable on-chain", system.name());
+    if system == ProofSystem::Compressed {
+        let proof_path = output
+            .files
+            .as_ref()
+            .and_then(|files| files.artifacts.as_ref())
+            .map_or("<FILE>".to_string(), |artifacts| {
+                artifacts.proof_path.display().to_string()
+            });
+        status!(
+            "   Wrap it into Groth16 with: starknet convert --wrap groth16 --proof {proof_path}"
+        );
+    }
+    Ok(())
+}
+
+/// Print where the fixture went and what to do next.
+fn report_fixture(output: &FixtureOutput, args: &OutputArgs) -> Result<()> {
+    match output.calldata_len {
+        Some(len) => status!("✅ Generated {} calldata elements", len),
+        None => status!("⚠️  No Starknet calldata generated for this proof system"),
+    }
 
-    // Extract the public values from the proof
-    let bytes = proof.public_values.as_slice();
+    if args.stdout {
+        println!("{}", serde_json::to_string_pretty(&output.fixture)?);
+    }
 
-    // Create a comprehensive test fixture with all proof data
-    let fixture = SP1FibonacciProofFixture {
-        vkey: vk.bytes32().to_string(),
-        public_values: format!("0x{}", hex::encode(bytes)),
-        proof: format!("0x{}", hex::encode(proof.bytes())),
+    let Some(files) = &output.files else {
+        status!("💡 Fixture files were not written (--no-fixture)");
+        return Ok(());
     };
 
-    // Display key information about the proof
-    println!("📋 Proof Information:");
-    println!("  Verification Key: {}", fixture.vkey);
-    println!("  Public Values: {}", fixture.public_values);
-    println!("  Proof Size: {} bytes", proof.bytes().len());
-
-    // Generate Starknet-compatible calldata using Garaga
-    println!("🔄 Converting to Starknet calldata...");
-    let calldata = get_sp1_garaga_starknet_calldata(proof, vk);
-    let calldata_len = calldata.len();
-    let calldata_hex_string = biguint_vec_to_hex_string(calldata);
-
-    println!("✅ Generated {} calldata elements", calldata_len);
-
-    // Determine the output directory for fixtures
-    let fixture_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../contracts/src/fixtures");
-    std::fs::create_dir_all(&fixture_path).expect("failed to create fixture path");
-
-    // Save the JSON fixture with complete proof metadata
-    let json_filename = format!("{:?}-fixture.json", system).to_lowercase();
-    std::fs::write(
-        fixture_path.join(&json_filename),
-        serde_json::to_string_pretty(&fixture).unwrap(),
-    )
-    .expect("failed to write JSON fixture");
-
-    // Save the calldata as a text file for easy loading in tests
-    let calldata_filename = format!("{:?}-calldata.txt", system).to_lowercase();
-    std::fs::write(fixture_path.join(&calldata_filename), calldata_hex_string)
-        .expect("failed to write calldata file");
-
-    println!("💾 Fixtures saved to: {}", fixture_path.display());
-    println!("   📄 {}", json_filename);
-    println!("   📄 {}", calldata_filename);
-
-    println!();
-    println!("🎯 Next Steps:");
-    println!("1. Run contract tests: cd ../contracts && snforge test");
-    println!("2. Verify the proof on-chain using the generated calldata");
-    println!("3. Integrate the verification into your application");
-
-    println!();
-    println!("💡 Integration Tips:");
-    println!("- Use the JSON fixture for comprehensive testing");
-    println!("- Use the calldata file for direct contract interactions");
-    println!("- Ensure your contract's SP1_PROGRAM matches the verification key");
+    status!("💾 Fixtures saved to: {}", files.dir.display());
+    status!("   📄 {}", files.json_path.display());
+    if let Some(calldata_path) = &files.calldata_path {
+        status!("   📄 {}", calldata_path.display());
+    }
+    if let Some(artifacts) = &files.artifacts {
+        status!("   📦 {}", artifacts.proof_path.display());
+        status!("   🔑 {}", artifacts.vk_path.display());
+    }
+
+    if files.calldata_path.is_none() {
+        return Ok(());
+    }
+
+    status!();
+    status!("🎯 Next Steps:");
+    status!("1. Run contract tests: cd ../contracts && snforge test");
+    status!("2. Verify the proof on-chain using the generated calldata");
+    status!("3. Integrate the verification into your application");
+
+    status!();
+    status!("💡 Integration Tips:");
+    status!("- Use the JSON fixture for comprehensive testing");
+    status!("- Use the calldata file for direct contract interactions");
+    status!("- Ensure your contract's SP1_PROGRAM matches the verification key");
+
+    Ok(())
 }
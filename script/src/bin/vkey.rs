@@ -11,6 +11,35 @@
 //! cargo run --release --bin vkey
 //! ```
 //!
+//! ### Regenerate the Cairo constant:
+//! ```bash
+//! cargo run --release --bin vkey -- --write
+//! ```
+//!
+//! ### Fail in CI when the checked-in constant is stale:
+//! ```bash
+//! cargo run --release --bin vkey -- --check
+//! ```
+//!
+//! ### Use an ELF built elsewhere instead of the embedded program:
+//! ```bash
+//! cargo run --release --bin vkey -- --elf ../other/elf/program
+//! ```
+//!
+//! ### Build a registry of several programs:
+//! ```bash
+//! cargo run --release --bin vkey -- --program fibonacci-program --program other=../other/elf/program
+//! ```
+//!
+//! Each `--program` is a guest build target of this workspace, an ELF path, or
+//! `NAME=PATH`. The registry is written to `contracts/src/generated/` as
+//! `program_registry.json` and `program_registry.cairo` (override with
+//! `--registry-dir`).
+//!
+//! Proving and verifying keys are cached in `target/key-cache`, so only the first
+//! run for a given ELF and SP1 version performs the program setup (disable with
+//! `--no-key-cache`).
+//!
 //! ## Output
 //!
 //! The script outputs a hexadecimal string representing the verification key:
@@ -20,26 +49,100 @@
 //!
 //! ## Integration
 //!
-//! Copy this verification key and update the `SP1_PROGRAM` constant in your
-//! Starknet verification contract (`contracts/src/lib.cairo`):
+//! The verification contract (`contracts/src/lib.cairo`) imports the `SP1_PROGRAM`
+//! constant from the generated module `contracts/src/generated/program_vkey.cairo`:
 //!
 //! ```cairo
-//! const SP1_PROGRAM: u256 = 0x00ee2a4a1c9c659ed802a544aa469136e72e1a1538af94fce56705576b48f247;
+//! pub const SP1_PROGRAM: u256 = 0x00ee2a4a1c9c659ed802a544aa469136e72e1a1538af94fce56705576b48f247;
 //! ```
 //!
+//! `--write` regenerates that module and `--check` exits with code 16 when it does
+//! not match the current ELF.
+//!
 //! ## Security Note
 //!
 //! The verification key is derived from the compiled SP1 program binary and
 //! changes whenever the program logic is modified. Always regenerate and update
 //! the verification key after making changes to the SP1 program.
 
-use sp1_sdk::{include_elf, HashableKey, Prover, ProverClient};
+use clap::Parser;
+use fibonacci_starknet::cache::KeyCache;
+use fibonacci_starknet::cairo::{
+    check_program_vkey, default_generated_dir, default_program_vkey_path, write_program_vkey,
+};
+use fibonacci_starknet::program::Program;
+use fibonacci_starknet::registry::{ProgramEntry, ProgramRegistry};
+use fibonacci_starknet::{Error, Result};
+use sp1_sdk::{CpuProver, HashableKey, Prover, ProverClient, SP1VerifyingKey};
+use std::path::PathBuf;
+use std::process::ExitCode;
 
-/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
-///
-/// This is the same program binary used in the main execution and proving scripts.
-/// The verification key is derived from this compiled program.
-pub const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci-program");
+/// Command-line arguments for verification key extraction.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct VkeyArgs {
+    /// Write the generated Cairo module defining `SP1_PROGRAM`.
+    #[arg(long, conflicts_with = "check")]
+    write: bool,
+
+    /// Exit non-zero if the generated Cairo module does not match the current ELF.
+    #[arg(long)]
+    check: bool,
+
+    /// Path of an SP1 program ELF to use instead of the embedded Fibonacci program.
+    #[arg(long, value_name = "FILE")]
+    elf: Option<PathBuf>,
+
+    /// Path of the generated Cairo module.
+    ///
+    /// Defaults to `contracts/src/generated/program_vkey.cairo` in the repository
+    /// checkout this binary was built from.
+    #[arg(long, value_name = "FILE")]
+    cairo_out: Option<PathBuf>,
+
+    /// Write a registry of several programs instead of the `SP1_PROGRAM` module.
+    ///
+    /// Accepts a guest build target (e.g. `fibonacci-program`), an ELF path, or
+    /// `NAME=PATH`. Repeat the option for every program.
+    #[arg(
+        long = "program",
+        value_name = "PROGRAM",
+        conflicts_with_all = ["elf", "write", "check", "cairo_out"]
+    )]
+    programs: Vec<String>,
+
+    /// Directory the registry files are written to.
+    ///
+    /// Defaults to `contracts/src/generated` in the repository checkout this
+    /// binary was built from.
+    #[arg(long, value_name = "DIR", requires = "programs")]
+    registry_dir: Option<PathBuf>,
+
+    /// Always run the program setup, without reading or writing the key cache.
+    #[arg(long)]
+    no_key_cache: bool,
+
+    /// Directory of the proving and verifying key cache.
+    ///
+    /// Defaults to `target/key-cache` in the repository checkout this binary
+    /// was built from.
+    #[arg(long, value_name = "DIR", env = "SP1_KEY_CACHE_DIR")]
+    key_cache_dir: Option<PathBuf>,
+}
+
+impl VkeyArgs {
+    /// Derive the verification key of `program`, reusing cached keys if enabled.
+    fn derive_vk(&self, prover: &CpuProver, program: &Program) -> Result<SP1VerifyingKey> {
+        if self.no_key_cache {
+            return Ok(prover.setup(&program.elf).1);
+        }
+        let key_cache = self
+            .key_cache_dir
+            .clone()
+            .map_or_else(KeyCache::default, KeyCache::new);
+        key_cache.vk(program, prover.version(), |elf| prover.setup(elf))
+    }
+}
 
 /// Extract and display the verification key for the SP1 Fibonacci program.
 ///
@@ -47,7 +150,7 @@ pub const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci-program");
 /// 1. Creates a CPU-based prover client (faster for key generation)
 /// 2. Sets up the program to generate the verification key
 /// 3. Extracts the verification key in hexadecimal format
-/// 4. Displays the key for use in the verification contract
+/// 4. Displays the key, or writes/checks the generated Cairo module
 ///
 /// ## Key Properties
 ///
@@ -56,7 +159,31 @@ pub const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci-program");
 /// - Is deterministic (same program = same key)
 /// - Is required for on-chain proof verification
 /// - Must match between proof generation and verification
-fn main() {
+fn main() -> ExitCode {
+    let args = VkeyArgs::parse();
+
+    let result = if args.programs.is_empty() {
+        extract_vkey(args)
+    } else {
+        write_registry(&args)
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("❌ Error: {error}");
+            if matches!(error, Error::StaleProgramVkey { .. }) {
+                eprintln!("   Regenerate it with: cargo run --release --bin vkey -- --write");
+            }
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+/// Derive the verification key of the program, then print, write or check it.
+fn extract_vkey(args: VkeyArgs) -> Result<()> {
+    let program = Program::from_elf_arg(args.elf.as_deref())?;
+
     println!("🔑 Extracting SP1 program verification key...");
 
     // Create a CPU-based prover for faster key generation
@@ -64,8 +191,9 @@ fn main() {
     let prover = ProverClient::builder().cpu().build();
 
     // Set up the program and extract the verification key
-    // This process analyzes the program binary and generates the corresponding key
-    let (_, vk) = prover.setup(FIBONACCI_ELF);
+    // This process analyzes the program binary and generates the corresponding key,
+    // unless it was cached by a previous run
+    let vk = args.derive_vk(&prover, &program)?;
 
     // Convert the verification key to a hexadecimal string format
     // This format is compatible with both Rust and Cairo contracts
@@ -76,12 +204,73 @@ fn main() {
     println!("📋 Verification Key:");
     println!("{}", vkey_hex);
     println!();
+
+    let cairo_path = args.cairo_out.unwrap_or_else(default_program_vkey_path);
+
+    if args.check {
+        check_program_vkey(&vkey_hex, &cairo_path)?;
+        println!("✅ {} is up to date", cairo_path.display());
+        return Ok(());
+    }
+
+    if args.write {
+        write_program_vkey(&vkey_hex, &cairo_path)?;
+        println!("💾 Wrote {}", cairo_path.display());
+        println!();
+        println!("📝 Next Steps:");
+        println!("1. Commit the generated Cairo module");
+        println!("2. Regenerate proofs if the key has changed");
+        return Ok(());
+    }
+
     println!("📝 Next Steps:");
-    println!("1. Copy the verification key above");
-    println!("2. Update contracts/src/lib.cairo:");
-    println!("   const SP1_PROGRAM: u256 = {};", vkey_hex);
-    println!("3. Regenerate proofs if the key has changed");
+    println!("1. Update the generated Cairo constant:");
+    println!("   cargo run --release --bin vkey -- --write");
+    println!("2. Regenerate proofs if the key has changed");
     println!();
     println!("💡 Note: This key uniquely identifies your SP1 program.");
     println!("   It will change if you modify the program logic.");
+
+    Ok(())
+}
+
+/// Derive the verification key of every `--program` and write the registry files.
+fn write_registry(args: &VkeyArgs) -> Result<()> {
+    let prover = ProverClient::builder().cpu().build();
+    let mut registry = ProgramRegistry::default();
+
+    for spec in &args.programs {
+        let program = Program::from_spec(spec)?;
+        println!("🔑 Extracting verification key of {}...", program.name);
+        let vk = args.derive_vk(&prover, &program)?;
+        registry.push(ProgramEntry::new(&program, &vk, prover.version()))?;
+    }
+
+    println!("✅ Verification keys extracted successfully!");
+    println!();
+    println!("📋 Program Registry:");
+    for entry in &registry.programs {
+        println!("  {}", entry.name);
+        println!("    Verification Key: {}", entry.vkey);
+        println!("    ELF SHA-256: {}", entry.elf_sha256);
+        println!("    SP1 Version: {}", entry.sp1_version);
+    }
+
+    let out_dir = args
+        .registry_dir
+        .clone()
+        .unwrap_or_else(default_generated_dir);
+    let files = registry.write(&out_dir)?;
+
+    println!();
+    println!("💾 Registry saved to: {}", out_dir.display());
+    println!("   📄 {}", files.json_path.display());
+    println!("   📄 {}", files.cairo_path.display());
+    println!();
+    println!("📝 Next Steps:");
+    println!("1. Declare the module in contracts/src/generated.cairo:");
+    println!("   pub mod program_registry;");
+    println!("2. Allowlist programs with program_registry::is_registered_program(vk)");
+
+    Ok(())
 }
@@ -0,0 +1,280 @@
+//! # Proof and Key Caches
+//!
+//! Content-addressed on-disk caches of generated proofs and program keys.
+//!
+//! Proving the same program on the same input twice yields an equivalent proof,
+//! so the binaries look proofs up by a [`ProofCacheKey`] before proving. The key
+//! is the SHA-256 digest of everything a proof depends on: the ELF, the
+//! serialized `SP1Stdin`, the proof system and the SP1 circuit version. Entries
+//! are stored as `{key}.bin` with `SP1ProofWithPublicValues::save`.
+//!
+//! Likewise, the proving and verifying keys returned by `client.setup` only
+//! depend on the ELF and the SP1 circuit version. [`KeyCache`] keeps them as
+//! `{elf_sha256}-{sp1_version}-pk.bin` and `-vk.bin`, so repeated runs skip the
+//! setup and the `vkey` binary does not need to run it at all.
+
+use crate::artifacts::{load_vk, save_vk};
+use crate::error::{Error, Result};
+use crate::fixture::ProofSystem;
+use crate::program::Program;
+use sha2::{Digest, Sha256};
+use sp1_sdk::{SP1ProofWithPublicValues, SP1ProvingKey, SP1Stdin, SP1VerifyingKey};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Directory of the proof cache of this workspace.
+///
+/// Lives under the Cargo `target` directory, so `cargo clean` also clears it.
+/// Like [`crate::fixture::default_fixture_dir`], this path is resolved at
+/// compile time and only makes sense in a checkout of the repository.
+pub fn default_cache_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../target/proof-cache")
+}
+
+/// Directory of the proving and verifying key cache of this workspace.
+///
+/// Lives next to [`default_cache_dir`], under the Cargo `target` directory.
+pub fn default_key_cache_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../target/key-cache")
+}
+
+/// The address of a proof in the [`ProofCache`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProofCacheKey(String);
+
+impl ProofCacheKey {
+    /// The key of a proof of `program` on `stdin`.
+    ///
+    /// `sp1_version` is the SP1 circuit version of the prover, as returned by
+    /// `Prover::version`, so that upgrading SP1 never returns stale proofs.
+    pub fn new(
+        program: &Program,
+        stdin: &SP1Stdin,
+        system: ProofSystem,
+        sp1_version: &str,
+    ) -> Result<Self> {
+        let stdin_bytes = bincode::serialize(stdin).map_err(|source| Error::Bincode {
+            path: PathBuf::from("<stdin>"),
+            source,
+        })?;
+
+        let mut hasher = Sha256::new();
+        for part in [
+            program.elf.as_ref(),
+            &stdin_bytes,
+            system.name().as_bytes(),
+            sp1_version.as_bytes(),
+        ] {
+            // Length-prefix every part so that their boundaries are unambiguous
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part);
+        }
+
+        Ok(Self(hex::encode(hasher.finalize())))
+    }
+}
+
+impl fmt::Display for ProofCacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Result of [`ProofCache::prune`] and [`KeyCache::prune`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruneStats {
+    /// Number of cached files removed.
+    pub removed: usize,
+    /// Total size of the removed files, in bytes.
+    pub freed_bytes: u64,
+    /// Number of cached files kept.
+    pub kept: usize,
+}
+
+/// An on-disk cache of `SP1ProofWithPublicValues`.
+#[derive(Debug, Clone)]
+pub struct ProofCache {
+    dir: PathBuf,
+}
+
+impl Default for ProofCache {
+    fn default() -> Self {
+        Self::new(default_cache_dir())
+    }
+}
+
+impl ProofCache {
+    /// A cache storing its entries in `dir`, created on first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory holding the cached proofs.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the cache entry for `key`.
+    pub fn path(&self, key: &ProofCacheKey) -> PathBuf {
+        self.dir.join(format!("{key}.bin"))
+    }
+
+    /// The cached proof for `key`, if any.
+    ///
+    /// Entries that can no longer be loaded, e.g. because they were truncated,
+    /// are treated as missing and overwritten by the next [`ProofCache::store`].
+    pub fn load(&self, key: &ProofCacheKey) -> Option<SP1ProofWithPublicValues> {
+        let path = self.path(key);
+        if !path.is_file() {
+            return None;
+        }
+        SP1ProofWithPublicValues::load(&path).ok()
+    }
+
+    /// Store a proof under `key`, returning the path of the entry.
+    pub fn store(&self, key: &ProofCacheKey, proof: &SP1ProofWithPublicValues) -> Result<PathBuf> {
+        std::fs::create_dir_all(&self.dir).map_err(Error::fixture_io(&self.dir))?;
+
+        let path = self.path(key);
+        proof.save(&path).map_err(|source| Error::ProofIo {
+            path: path.clone(),
+            source,
+        })?;
+        Ok(path)
+    }
+
+    /// Remove cached proofs, keeping those written less than `max_age` ago.
+    ///
+    /// Removes every entry when `max_age` is `None`. A missing cache directory
+    /// is an empty cache.
+    pub fn prune(&self, max_age: Option<Duration>) -> Result<PruneStats> {
+        prune_dir(&self.dir, max_age)
+    }
+}
+
+/// An on-disk cache of the `SP1ProvingKey` and `SP1VerifyingKey` of programs.
+#[derive(Debug, Clone)]
+pub struct KeyCache {
+    dir: PathBuf,
+}
+
+impl Default for KeyCache {
+    fn default() -> Self {
+        Self::new(default_key_cache_dir())
+    }
+}
+
+impl KeyCache {
+    /// A cache storing its entries in `dir`, created on first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory holding the cached keys.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Paths of the proving and verifying key entries of `program`.
+    pub fn paths(&self, program: &Program, sp1_version: &str) -> (PathBuf, PathBuf) {
+        let digest = program.elf_sha256();
+        let stem = format!("{}-{sp1_version}", digest.trim_start_matches("0x"));
+        (
+            self.dir.join(format!("{stem}-pk.bin")),
+            self.dir.join(format!("{stem}-vk.bin")),
+        )
+    }
+
+    /// The keys of `program`, running `setup` and caching its result on a miss.
+    ///
+    /// `setup` is usually `|elf| client.setup(elf)`, and `sp1_version` the
+    /// `Prover::version` of the same client. Cached proving keys are only used
+    /// if they were generated for the same ELF bytes.
+    pub fn setup(
+        &self,
+        program: &Program,
+        sp1_version: &str,
+        setup: impl FnOnce(&[u8]) -> (SP1ProvingKey, SP1VerifyingKey),
+    ) -> Result<(SP1ProvingKey, SP1VerifyingKey)> {
+        let (pk_path, vk_path) = self.paths(program, sp1_version);
+        let cached_pk = std::fs::read(&pk_path)
+            .ok()
+            .and_then(|bytes| bincode::deserialize::<SP1ProvingKey>(&bytes).ok())
+            .filter(|pk| pk.elf[..] == program.elf[..]);
+        if let (Some(pk), Ok(vk)) = (cached_pk, load_vk(&vk_path)) {
+            return Ok((pk, vk));
+        }
+
+        let (pk, vk) = setup(&program.elf);
+        std::fs::create_dir_all(&self.dir).map_err(Error::fixture_io(&self.dir))?;
+        let bytes = bincode::serialize(&pk).map_err(|source| Error::Bincode {
+            path: pk_path.clone(),
+            source,
+        })?;
+        std::fs::write(&pk_path, bytes).map_err(Error::fixture_io(&pk_path))?;
+        save_vk(&vk, &vk_path)?;
+
+        Ok((pk, vk))
+    }
+
+    /// The verifying key of `program`, without loading the proving key.
+    ///
+    /// Falls back to [`KeyCache::setup`] on a miss, which caches both keys.
+    pub fn vk(
+        &self,
+        program: &Program,
+        sp1_version: &str,
+        setup: impl FnOnce(&[u8]) -> (SP1ProvingKey, SP1VerifyingKey),
+    ) -> Result<SP1VerifyingKey> {
+        let (_, vk_path) = self.paths(program, sp1_version);
+        if let Ok(vk) = load_vk(&vk_path) {
+            return Ok(vk);
+        }
+        Ok(self.setup(program, sp1_version, setup)?.1)
+    }
+
+    /// Remove cached keys, keeping those written less than `max_age` ago.
+    ///
+    /// Behaves like [`ProofCache::prune`].
+    pub fn prune(&self, max_age: Option<Duration>) -> Result<PruneStats> {
+        prune_dir(&self.dir, max_age)
+    }
+}
+
+/// Remove the `.bin` files of a cache directory older than `max_age`, or all of them.
+fn prune_dir(dir: &Path, max_age: Option<Duration>) -> Result<PruneStats> {
+    let mut stats = PruneStats::default();
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(stats),
+        Err(error) => return Err(Error::fixture_io(dir)(error)),
+    };
+
+    let now = SystemTime::now();
+    for entry in entries {
+        let entry = entry.map_err(Error::fixture_io(dir))?;
+        let path = entry.path();
+        if path.extension().is_none_or(|extension| extension != "bin") {
+            continue;
+        }
+
+        let metadata = entry.metadata().map_err(Error::fixture_io(&path))?;
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .unwrap_or_default();
+        if max_age.is_some_and(|max_age| age < max_age) {
+            stats.kept += 1;
+            continue;
+        }
+
+        std::fs::remove_file(&path).map_err(Error::fixture_io(&path))?;
+        stats.removed += 1;
+        stats.freed_bytes += metadata.len();
+    }
+
+    Ok(stats)
+}
@@ -0,0 +1,89 @@
+//! # Generated Cairo Code
+//!
+//! Cairo modules generated from the SP1 program, so the verification contract never
+//! ships with a hand-edited, stale `SP1_PROGRAM` constant.
+//!
+//! The `vkey` binary writes [`program_vkey_module`] to
+//! `contracts/src/generated/program_vkey.cairo` and, in `--check` mode, compares
+//! the checked-in file with the verification key of the current ELF.
+
+use crate::error::{Error, Result};
+use std::path::{Path, PathBuf};
+
+/// The directory of generated Cairo modules in the contracts of this repository.
+///
+/// Like [`crate::fixture::default_fixture_dir`], this path is resolved at compile
+/// time and only makes sense in a checkout of the repository.
+pub fn default_generated_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../contracts/src/generated")
+}
+
+/// The generated module containing `SP1_PROGRAM`, in the contracts of this repository.
+pub fn default_program_vkey_path() -> PathBuf {
+    default_generated_dir().join("program_vkey.cairo")
+}
+
+/// Contents of the generated Cairo module defining `SP1_PROGRAM`.
+///
+/// ## Example
+///
+/// ```rust
+/// use fibonacci_starknet::cairo::{program_vkey_module, read_program_vkey};
+///
+/// let vkey = "0x00ee2a4a1c9c659ed802a544aa469136e72e1a1538af94fce56705576b48f247";
+/// let module = program_vkey_module(vkey);
+/// assert!(module.contains(&format!("pub const SP1_PROGRAM: u256 = {vkey};")));
+/// assert_eq!(read_program_vkey(&module), Some(vkey));
+/// ```
+pub fn program_vkey_module(vkey_hash: &str) -> String {
+    format!(
+        "// @generated by `cargo run --release --bin vkey -- --write` in the script directory.
+// Do not edit by hand: regenerate after every change to the SP1 program.
+
+/// Verification key for the specific SP1 program this contract accepts.
+///
+/// This key uniquely identifies the SP1 program (Fibonacci computation in this example).
+/// Only proofs generated for this specific program will be accepted by the contract.
+pub const SP1_PROGRAM: u256 = {vkey_hash};
+"
+    )
+}
+
+/// Extract the `SP1_PROGRAM` value from the contents of a generated module.
+pub fn read_program_vkey(module: &str) -> Option<&str> {
+    module.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("pub const SP1_PROGRAM: u256 = ")?
+            .strip_suffix(';')
+    })
+}
+
+/// Write the generated `SP1_PROGRAM` module, creating its directory if needed.
+pub fn write_program_vkey(vkey_hash: &str, path: &Path) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(Error::fixture_io(dir))?;
+    }
+    std::fs::write(path, program_vkey_module(vkey_hash)).map_err(Error::fixture_io(path))
+}
+
+/// Check that the generated module at `path` is up to date with `vkey_hash`.
+///
+/// ## Errors
+///
+/// Returns [`Error::StaleProgramVkey`] if the file is missing, was edited by
+/// hand, or holds another verification key.
+pub fn check_program_vkey(vkey_hash: &str, path: &Path) -> Result<()> {
+    let current = std::fs::read_to_string(path).ok();
+    if current.as_deref() == Some(program_vkey_module(vkey_hash).as_str()) {
+        return Ok(());
+    }
+
+    Err(Error::StaleProgramVkey {
+        path: path.to_path_buf(),
+        expected: vkey_hash.to_string(),
+        found: current
+            .as_deref()
+            .and_then(read_program_vkey)
+            .map(str::to_string),
+    })
+}
@@ -0,0 +1,255 @@
+//! # Starknet Calldata Generation
+//!
+//! Conversion of SP1 Groth16 proofs into calldata for the Garaga SP1 Verifier,
+//! plus helpers for formatting that calldata for Starknet Foundry tests.
+//!
+//! Calldata is available either as raw `BigUint` values or as range-checked
+//! felt252 values ([`Felt`]) for backends such as starkli.
+
+use crate::error::{Error, Result};
+use crate::fixture::ProofSystem;
+use clap::ValueEnum;
+use garaga_rs::calldata::full_proof_with_hints::groth16::{
+    get_groth16_calldata, get_sp1_vk, Groth16Proof,
+};
+use garaga_rs::definitions::CurveID;
+use num_bigint::BigUint;
+use sp1_sdk::{HashableKey, SP1Proof, SP1ProofWithPublicValues, SP1VerifyingKey};
+use starknet_types_core::felt::Felt;
+
+/// Size of an encoded SP1 Groth16 proof: a 4-byte verifier selector followed by
+/// the eight 32-byte words of the A, B and C points.
+pub const SP1_GROTH16_PROOF_LEN: usize = 4 + 8 * 32;
+
+/// Numeric representation used when producing calldata.
+///
+/// Both formats serialize to the same text output; they differ in the type
+/// produced by the library and in the validation applied to each element.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Default)]
+pub enum CalldataFormat {
+    /// Raw `BigUint` values, exactly as returned by Garaga.
+    #[default]
+    Biguint,
+
+    /// felt252 values, each checked against the Starknet field modulus.
+    ///
+    /// Best suited for backends that consume `Felt` directly, such as
+    /// [starkli](https://github.com/xJonathanLEI/starkli).
+    Felt,
+}
+
+/// Convert SP1 proof to Starknet-compatible calldata using Garaga.
+///
+/// This function transforms an SP1 proof into the format expected by the
+/// Garaga SP1 Verifier on Starknet. The conversion process:
+///
+/// 1. Extracts the verification key from the SP1 proof
+/// 2. Creates a Garaga-compatible Groth16 proof structure
+/// 3. Generates calldata formatted for Starknet contract calls
+///
+/// ## Parameters
+///
+/// - `proof`: The SP1 proof with public values
+/// - `vk`: The SP1 verification key
+///
+/// ## Returns
+///
+/// A vector of `BigUint` values representing the calldata that can be
+/// passed to the Starknet verification contract.
+///
+/// ## Errors
+///
+/// Returns [`Error::UnsupportedOnStarknet`] for anything but a Groth16 proof,
+/// [`Error::VkeyDecode`] if the verification key hash is not valid hex and
+/// [`Error::Garaga`] if Garaga rejects the proof during calldata generation.
+///
+/// ## Garaga Integration
+///
+/// This function uses the Garaga library to:
+/// - Convert SP1 proof format to Groth16 format
+/// - Generate BN254 curve-compatible calldata
+/// - Ensure compatibility with the on-chain verifier
+pub fn get_sp1_garaga_starknet_calldata(
+    proof: &SP1ProofWithPublicValues,
+    vk: &SP1VerifyingKey,
+) -> Result<Vec<BigUint>> {
+    // Only Groth16 proofs can be checked by the Garaga SP1 Verifier
+    if !matches!(proof.proof, SP1Proof::Groth16(_)) {
+        return Err(Error::UnsupportedOnStarknet(
+            ProofSystem::of_proof(&proof.proof).name(),
+        ));
+    }
+
+    sp1_groth16_calldata(
+        &vk.bytes32(),
+        proof.public_values.as_slice(),
+        &proof.bytes(),
+    )
+}
+
+/// Convert the raw parts of an SP1 Groth16 proof to Starknet calldata.
+///
+/// This is the building block behind [`get_sp1_garaga_starknet_calldata`]. It
+/// works from the hex-decoded contents of a proof fixture, so calldata can be
+/// regenerated without the original `SP1ProofWithPublicValues`.
+///
+/// ## Parameters
+///
+/// - `vkey_hash`: The `0x`-prefixed program verification key hash (`vk.bytes32()`)
+/// - `public_values`: The public values committed by the program
+/// - `proof_bytes`: The encoded Groth16 proof (`proof.bytes()`)
+///
+/// ## Errors
+///
+/// Returns [`Error::VkeyDecode`] if the verification key hash is not valid hex
+/// and [`Error::Garaga`] if the proof is malformed or rejected by Garaga.
+pub fn sp1_groth16_calldata(
+    vkey_hash: &str,
+    public_values: &[u8],
+    proof_bytes: &[u8],
+) -> Result<Vec<BigUint>> {
+    // Get the SP1 Groth16 verification key from Garaga
+    // This is the universal verification key for SP1 Groth16 proofs
+    let sp1_groth16_vk = get_sp1_vk();
+
+    // Extract the program verification key as bytes
+    // This identifies the specific SP1 program being proven
+    let vkey_bytes = decode_vkey_hash(vkey_hash)?;
+
+    // Garaga expects the 4-byte verifier selector followed by the 8 proof words
+    if proof_bytes.len() != SP1_GROTH16_PROOF_LEN {
+        return Err(Error::Garaga(format!(
+            "expected a {SP1_GROTH16_PROOF_LEN}-byte SP1 Groth16 proof, got {} bytes",
+            proof_bytes.len()
+        )));
+    }
+
+    // Create a Garaga-compatible Groth16 proof from the SP1 proof
+    // This conversion handles the format differences between SP1 and Garaga
+    let groth16_proof =
+        Groth16Proof::from_sp1(vkey_bytes, public_values.to_vec(), proof_bytes.to_vec());
+
+    // Generate Starknet calldata for the proof
+    // This creates the properly formatted data for contract calls
+    // See `get_sp1_garaga_starknet_calldata_felt` for a `Vec<Felt>` variant.
+    get_groth16_calldata(&groth16_proof, &sp1_groth16_vk, CurveID::BN254).map_err(Error::Garaga)
+}
+
+/// Convert SP1 proof to Starknet calldata as felt252 values.
+///
+/// This is the `Vec<Felt>` counterpart of [`get_sp1_garaga_starknet_calldata`],
+/// for better backend integration with tools like
+/// [starkli](https://github.com/xJonathanLEI/starkli).
+///
+/// Each element is range-checked with [`biguint_vec_to_felts`] before conversion,
+/// so calldata that cannot be represented on Starknet is rejected rather than
+/// silently reduced modulo the field prime.
+///
+/// ## Errors
+///
+/// Returns the same errors as [`get_sp1_garaga_starknet_calldata`], plus
+/// [`Error::FeltOutOfRange`] if an element does not fit in a felt252.
+pub fn get_sp1_garaga_starknet_calldata_felt(
+    proof: &SP1ProofWithPublicValues,
+    vk: &SP1VerifyingKey,
+) -> Result<Vec<Felt>> {
+    let calldata = get_sp1_garaga_starknet_calldata(proof, vk)?;
+    biguint_vec_to_felts(&calldata)
+}
+
+/// Convert calldata values to felt252, rejecting any value outside the field.
+///
+/// A value is representable if it is strictly smaller than the Starknet field
+/// modulus `P = 2^251 + 17 * 2^192 + 1`.
+///
+/// ## Example
+///
+/// ```rust
+/// use fibonacci_starknet::calldata::biguint_vec_to_felts;
+/// use num_bigint::BigUint;
+///
+/// let felts = biguint_vec_to_felts(&[BigUint::from(255u32)]).unwrap();
+/// assert_eq!(format!("{:#x}", felts[0]), "0xff");
+///
+/// let too_large = BigUint::from(1u32) << 252;
+/// assert!(biguint_vec_to_felts(&[too_large]).is_err());
+/// ```
+pub fn biguint_vec_to_felts(calldata: &[BigUint]) -> Result<Vec<Felt>> {
+    let modulus = Felt::MAX.to_biguint() + 1u32;
+
+    calldata
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            if *value >= modulus {
+                return Err(Error::FeltOutOfRange {
+                    index,
+                    value: value.clone(),
+                });
+            }
+            Ok(Felt::from_bytes_be_slice(&value.to_bytes_be()))
+        })
+        .collect()
+}
+
+/// Convert a slice of BigUint values to hexadecimal string format.
+///
+/// This function formats calldata for easy storage and loading in test fixtures.
+/// Each BigUint value is converted to a hexadecimal string and placed on its own line.
+///
+/// ## Parameters
+///
+/// - `calldata`: The BigUint values representing the proof calldata
+///
+/// ## Returns
+///
+/// A string where each line contains a hexadecimal representation of a BigUint value,
+/// suitable for saving to a text file and loading in Cairo tests.
+///
+/// ## Format
+///
+/// The output format is compatible with Starknet Foundry's `read_txt` function:
+/// ```text
+/// 0xff
+/// 0xfff
+/// 0x1234
+/// ```
+///
+/// ## Example
+///
+/// ```rust
+/// use fibonacci_starknet::biguint_vec_to_hex_string;
+/// use num_bigint::BigUint;
+///
+/// let calldata = vec![BigUint::from(255u32), BigUint::from(4095u32)];
+/// let hex_string = biguint_vec_to_hex_string(&calldata);
+/// assert_eq!(hex_string, "0xff\n0xfff\n");
+/// ```
+pub fn biguint_vec_to_hex_string(calldata: &[BigUint]) -> String {
+    calldata
+        .iter()
+        .map(|big_uint| format!("0x{:x}", big_uint))
+        .collect::<Vec<String>>()
+        .join("\n")
+        + "\n" // Add final newline for proper file formatting
+}
+
+/// Convert a slice of felt252 values to hexadecimal string format.
+///
+/// Produces the same `read_txt`-compatible output as [`biguint_vec_to_hex_string`].
+pub fn felt_vec_to_hex_string(calldata: &[Felt]) -> String {
+    calldata
+        .iter()
+        .map(|felt| format!("{:#x}", felt))
+        .collect::<Vec<String>>()
+        .join("\n")
+        + "\n" // Add final newline for proper file formatting
+}
+
+/// Decode a `0x`-prefixed verification key hash into its 32 bytes.
+pub(crate) fn decode_vkey_hash(vkey_hash: &str) -> Result<Vec<u8>> {
+    hex::decode(vkey_hash.trim_start_matches("0x")).map_err(|source| Error::VkeyDecode {
+        vkey: vkey_hash.to_string(),
+        source,
+    })
+}
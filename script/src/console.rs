@@ -0,0 +1,34 @@
+//! # Console Output
+//!
+//! Progress messages printed by the binaries.
+//!
+//! Messages go to stdout by default. When stdout carries machine-readable output
+//! (for example the fixture JSON printed by `starknet --stdout`), call
+//! [`redirect_to_stderr`] so that the [`status!`](crate::status) macro keeps
+//! stdout clean.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Send all subsequent [`status!`](crate::status) messages to stderr.
+pub fn redirect_to_stderr() {
+    TO_STDERR.store(true, Ordering::Relaxed);
+}
+
+/// Whether [`status!`](crate::status) messages currently go to stderr.
+pub fn is_redirected() -> bool {
+    TO_STDERR.load(Ordering::Relaxed)
+}
+
+/// Print a progress message, like `println!`, honouring [`redirect_to_stderr`].
+#[macro_export]
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if $crate::console::is_redirected() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
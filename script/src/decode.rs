@@ -0,0 +1,475 @@
+//! # Calldata Decoding
+//!
+//! Parsing of Garaga SP1 Groth16 calldata back into its components, so a fixture
+//! can be inspected and diagnosed without deploying anything.
+//!
+//! The calldata passed to `verify_sp1_proof` is laid out as follows:
+//!
+//! | Section       | Felts          | Contents                                       |
+//! |---------------|----------------|------------------------------------------------|
+//! | Span length   | 1              | Number of felts that follow                    |
+//! | A (G1)        | 8              | x and y, each as four 96-bit limbs             |
+//! | B (G2)        | 16             | x0, x1, y0 and y1, each as four 96-bit limbs   |
+//! | C (G1)        | 8              | x and y, each as four 96-bit limbs             |
+//! | Program vkey  | 2              | `u256` as low and high 128-bit halves          |
+//! | Public values | 1 + 8 per word | Word count, then 32-byte words as `u32` limbs  |
+//! | MPCheck hints | see below      | Hints of the multi-pairing check               |
+//! | MSM hints     | 1 + len        | Hints of the public input MSM, as a span       |
+//!
+//! The pairing check hints hold the following blocks, where coefficients of
+//! `Fp12` elements and quotients (`u288`) are three 96-bit limbs each:
+//!
+//! | Block                 | Felts       | Contents                                   |
+//! |-----------------------|-------------|--------------------------------------------|
+//! | `lambda_root`         | 36          | `E12D`: 12 coefficients                    |
+//! | `lambda_root_inverse` | 36          | `E12D`: 12 coefficients                    |
+//! | `w`                   | 18          | Miller loop scaling factor: 6 coefficients |
+//! | `ris`                 | 1 + 36 each | Span of `E12D` Miller loop results         |
+//! | `big_q`               | 1 + 3 each  | Span of quotient coefficients              |
+//! | `z`                   | 1           | Random evaluation point (felt252)          |
+//! | `small_q`             | 33          | `E12DMulQuotient`: 11 coefficients         |
+
+use crate::calldata::SP1_GROTH16_PROOF_LEN;
+use crate::error::{Error, Result};
+use crate::fixture::SP1FibonacciProofFixture;
+use num_bigint::BigUint;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Number of felts before the public values: span length, A, B, C and vkey.
+const POINTS_AND_VKEY_LEN: usize = 1 + 8 + 16 + 8 + 2;
+
+/// Number of felts of a `u288` hint coefficient: three 96-bit limbs.
+pub(crate) const U288_LEN: usize = 3;
+
+/// Number of felts of an `E12D` hint: twelve `u288` coefficients.
+pub(crate) const E12D_LEN: usize = 12 * U288_LEN;
+
+/// Number of felts of the Miller loop scaling factor `w`: six `u288` coefficients.
+pub(crate) const SCALING_FACTOR_LEN: usize = 6 * U288_LEN;
+
+/// Number of felts of the `small_q` quotient: eleven `u288` coefficients.
+pub(crate) const SMALL_Q_LEN: usize = 11 * U288_LEN;
+
+/// Bit size of the limbs of `u288` and `E12D` hints.
+const HINT_LIMB_BITS: u64 = 96;
+
+/// Bit size of a felt252.
+const FELT_BITS: u64 = 252;
+
+/// A BN254 G1 point, as `0x`-prefixed hex coordinates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct G1Point {
+    pub x: String,
+    pub y: String,
+}
+
+/// A BN254 G2 point, as `0x`-prefixed hex coordinates over Fp2.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct G2Point {
+    pub x0: String,
+    pub x1: String,
+    pub y0: String,
+    pub y1: String,
+}
+
+/// Location of one block of hints within the calldata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HintBlock {
+    /// Name of the block, as in Garaga's Cairo structs, e.g. `lambda_root`.
+    pub name: &'static str,
+    /// Index of the first felt of the block in the calldata.
+    pub offset: usize,
+    /// Number of felts of the block, including the length prefix of spans.
+    pub len: usize,
+    /// Number of elements of span blocks, as given by their length prefix.
+    pub items: Option<usize>,
+}
+
+/// Location of a group of hint blocks within the calldata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HintSection {
+    /// Index of the first felt of the section in the calldata.
+    pub offset: usize,
+    /// Number of felts of the section.
+    pub len: usize,
+    /// The blocks of the section, in calldata order.
+    pub blocks: Vec<HintBlock>,
+}
+
+/// The hints of the calldata, split into the blocks of Garaga's layout.
+///
+/// The hints only help the on-chain verifier run its pairing check and
+/// multi-scalar multiplication. Their values are not decoded, but every block is
+/// located and its limbs range-checked, so a broken fixture can be traced to the
+/// block it breaks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HintsSection {
+    /// Index of the first hint felt in the calldata.
+    pub offset: usize,
+    /// Number of hint felts.
+    pub len: usize,
+    /// Hints of the multi-pairing check (`mpcheck_hint` and `small_Q`).
+    pub mpcheck: HintSection,
+    /// Hints of the public input multi-scalar multiplication (`msm_hint`).
+    pub msm: HintSection,
+}
+
+/// Garaga SP1 Groth16 calldata, split into named sections.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecodedCalldata {
+    /// The span length prefix (number of felts after it).
+    pub span_len: usize,
+    /// Proof point A.
+    pub a: G1Point,
+    /// Proof point B.
+    pub b: G2Point,
+    /// Proof point C.
+    pub c: G1Point,
+    /// The program verification key hash, formatted like `vk.bytes32()`.
+    pub vkey: String,
+    /// The public inputs returned by the verifier, one 32-byte word each.
+    pub public_inputs: Vec<String>,
+    /// The public values committed by the program (the concatenated words).
+    pub public_values: String,
+    /// The hint block.
+    pub hints: HintsSection,
+}
+
+/// Result of comparing one calldata section with a fixture.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SectionCheck {
+    /// Name of the compared section, e.g. `vkey` or `a.x`.
+    pub section: &'static str,
+    /// The value derived from the fixture.
+    pub expected: String,
+    /// The value found in the calldata.
+    pub actual: String,
+    /// Whether both values match.
+    pub ok: bool,
+}
+
+/// Parse calldata written with the `hex` encoding (one `0x` value per line).
+///
+/// Blank lines are ignored.
+///
+/// ## Errors
+///
+/// Returns [`Error::InvalidCalldata`] if a line is not a hex number.
+pub fn parse_hex_calldata(text: &str) -> Result<Vec<BigUint>> {
+    text.lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            let digits = line.strip_prefix("0x").unwrap_or(line);
+            BigUint::parse_bytes(digits.as_bytes(), 16).ok_or_else(|| {
+                Error::InvalidCalldata(format!("line {} is not a hex number: `{line}`", index + 1))
+            })
+        })
+        .collect()
+}
+
+/// Split Garaga SP1 Groth16 calldata into its sections.
+///
+/// ## Errors
+///
+/// Returns [`Error::InvalidCalldata`] if the calldata is truncated, its span
+/// length does not match, or a limb is out of range for its section.
+///
+/// ## Example
+///
+/// ```rust
+/// use fibonacci_starknet::decode::decode_groth16_calldata;
+/// use fibonacci_starknet::mock::mock_groth16_calldata;
+///
+/// let vkey = format!("0x{}", "11".repeat(32));
+/// let calldata = mock_groth16_calldata(&vkey, &[7u8; 64]).unwrap();
+///
+/// let decoded = decode_groth16_calldata(&calldata).unwrap();
+/// assert_eq!(decoded.vkey, vkey);
+/// assert_eq!(decoded.public_inputs.len(), 2);
+/// assert_eq!(decoded.a.x, "0x1");
+/// assert_eq!(decoded.hints.mpcheck.blocks.len(), 7);
+/// assert_eq!(decoded.hints.msm.offset + decoded.hints.msm.len, calldata.len());
+/// ```
+pub fn decode_groth16_calldata(calldata: &[BigUint]) -> Result<DecodedCalldata> {
+    let mut reader = Reader {
+        calldata,
+        offset: 0,
+    };
+
+    let span_len = reader.usize("span length")?;
+    if span_len != calldata.len() - 1 {
+        return Err(Error::InvalidCalldata(format!(
+            "span length is {span_len} but {} felts follow it",
+            calldata.len() - 1
+        )));
+    }
+
+    let a = reader.g1("A")?;
+    let b = G2Point {
+        x0: reader.u384("B.x0")?,
+        x1: reader.u384("B.x1")?,
+        y0: reader.u384("B.y0")?,
+        y1: reader.u384("B.y1")?,
+    };
+    let c = reader.g1("C")?;
+
+    let low = reader.limb("vkey", 128)?;
+    let high = reader.limb("vkey", 128)?;
+    let vkey = format!("0x{:064x}", (high << 128) | low);
+    debug_assert_eq!(reader.offset, POINTS_AND_VKEY_LEN);
+
+    let words = reader.usize("public input count")?;
+    let mut public_inputs = Vec::with_capacity(words);
+    let mut public_values = Vec::with_capacity(words * 32);
+    for _ in 0..words {
+        let mut word = BigUint::ZERO;
+        for _ in 0..8 {
+            word = (word << 32) | reader.limb("public inputs", 32)?;
+        }
+        let bytes = word.to_bytes_be();
+        let mut padded = vec![0u8; 32 - bytes.len()];
+        padded.extend(bytes);
+
+        public_inputs.push(format!("0x{}", hex::encode(&padded)));
+        public_values.extend(padded);
+    }
+
+    let hints_offset = reader.offset;
+    let mpcheck = reader.section(|reader| {
+        Ok(vec![
+            reader.block("lambda_root", E12D_LEN, HINT_LIMB_BITS)?,
+            reader.block("lambda_root_inverse", E12D_LEN, HINT_LIMB_BITS)?,
+            reader.block("w", SCALING_FACTOR_LEN, HINT_LIMB_BITS)?,
+            reader.span("ris", E12D_LEN, HINT_LIMB_BITS)?,
+            reader.span("big_q", U288_LEN, HINT_LIMB_BITS)?,
+            reader.block("z", 1, FELT_BITS)?,
+            reader.block("small_q", SMALL_Q_LEN, HINT_LIMB_BITS)?,
+        ])
+    })?;
+    let msm = reader.section(|reader| Ok(vec![reader.span("msm_hint", 1, FELT_BITS)?]))?;
+
+    if reader.offset != calldata.len() {
+        return Err(Error::InvalidCalldata(format!(
+            "{} unexpected felts after the MSM hints, at offset {}",
+            calldata.len() - reader.offset,
+            reader.offset
+        )));
+    }
+    let hints = HintsSection {
+        offset: hints_offset,
+        len: calldata.len() - hints_offset,
+        mpcheck,
+        msm,
+    };
+
+    Ok(DecodedCalldata {
+        span_len,
+        a,
+        b,
+        c,
+        vkey,
+        public_inputs,
+        public_values: format!("0x{}", hex::encode(public_values)),
+        hints,
+    })
+}
+
+impl DecodedCalldata {
+    /// Compare every decoded section with the contents of a JSON fixture.
+    ///
+    /// The proof points are compared with the words of the fixture's encoded
+    /// proof, which SP1 lays out as A.x, A.y, B.x1, B.x0, B.y1, B.y0, C.x, C.y.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFixture`] if a fixture field is not valid hex. A
+    /// mismatch is not an error: it is reported through [`SectionCheck::ok`].
+    pub fn check_fixture(&self, fixture: &SP1FibonacciProofFixture) -> Result<Vec<SectionCheck>> {
+        let mut checks = vec![
+            check("vkey", &fixture.vkey, &self.vkey),
+            check("public_values", &fixture.public_values, &self.public_values),
+        ];
+
+        let proof = fixture.proof_bytes()?;
+        if proof.len() == SP1_GROTH16_PROOF_LEN {
+            let word = |i: usize| {
+                let start = 4 + 32 * i;
+                format!("{:#x}", BigUint::from_bytes_be(&proof[start..start + 32]))
+            };
+            let points = [
+                ("a.x", &self.a.x, 0),
+                ("a.y", &self.a.y, 1),
+                ("b.x1", &self.b.x1, 2),
+                ("b.x0", &self.b.x0, 3),
+                ("b.y1", &self.b.y1, 4),
+                ("b.y0", &self.b.y0, 5),
+                ("c.x", &self.c.x, 6),
+                ("c.y", &self.c.y, 7),
+            ];
+            checks.extend(
+                points
+                    .into_iter()
+                    .map(|(section, actual, i)| check(section, &word(i), actual)),
+            );
+        } else {
+            checks.push(check(
+                "proof",
+                &format!("{SP1_GROTH16_PROOF_LEN} bytes"),
+                &format!("{} bytes", proof.len()),
+            ));
+        }
+
+        Ok(checks)
+    }
+}
+
+/// Check that calldata commits to the expected program and public values.
+///
+/// Guards against stale or mixed-up fixtures: the vkey in the calldata must equal
+/// `vkey_hash`, and the SHA-256 digest of the public values in the calldata must
+/// equal the digest of `public_values`, which is what the SP1 verifier commits to.
+///
+/// ## Parameters
+///
+/// - `calldata`: Garaga SP1 Groth16 calldata
+/// - `vkey_hash`: The expected program verification key hash (`vk.bytes32()`)
+/// - `public_values`: The expected public values (`proof.public_values`)
+///
+/// ## Errors
+///
+/// Returns [`Error::CalldataMismatch`] on the first mismatching section, and
+/// [`Error::InvalidCalldata`] if the calldata cannot be decoded.
+pub fn check_calldata_consistency(
+    calldata: &[BigUint],
+    vkey_hash: &str,
+    public_values: &[u8],
+) -> Result<()> {
+    let decoded = decode_groth16_calldata(calldata)?;
+
+    let vkey = check("vkey", vkey_hash, &decoded.vkey);
+    let decoded_values = hex::decode(decoded.public_values.trim_start_matches("0x"))
+        .expect("decoded public values are valid hex");
+    let digest = check(
+        "public_values_digest",
+        &format!("0x{}", hex::encode(Sha256::digest(public_values))),
+        &format!("0x{}", hex::encode(Sha256::digest(decoded_values))),
+    );
+
+    match [vkey, digest].into_iter().find(|check| !check.ok) {
+        Some(mismatch) => Err(Error::CalldataMismatch {
+            section: mismatch.section,
+            expected: mismatch.expected,
+            actual: mismatch.actual,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Compare two formatted values.
+fn check(section: &'static str, expected: &str, actual: &str) -> SectionCheck {
+    SectionCheck {
+        section,
+        expected: expected.to_string(),
+        actual: actual.to_string(),
+        ok: expected.eq_ignore_ascii_case(actual),
+    }
+}
+
+/// Sequential reader over the calldata felts.
+struct Reader<'a> {
+    calldata: &'a [BigUint],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    /// Read one felt, checking that it fits in `bits` bits.
+    fn limb(&mut self, section: &str, bits: u64) -> Result<BigUint> {
+        let value = self.calldata.get(self.offset).ok_or_else(|| {
+            Error::InvalidCalldata(format!(
+                "calldata ends after {} felts, in section {section}",
+                self.offset
+            ))
+        })?;
+        if value.bits() > bits {
+            return Err(Error::InvalidCalldata(format!(
+                "felt {} ({value:#x}) in section {section} exceeds {bits} bits",
+                self.offset
+            )));
+        }
+        self.offset += 1;
+        Ok(value.clone())
+    }
+
+    /// Read a length or count.
+    fn usize(&mut self, section: &str) -> Result<usize> {
+        let value = self.limb(section, 32)?;
+        Ok(value.to_u32_digits().first().copied().unwrap_or(0) as usize)
+    }
+
+    /// Read a base field element stored as four little-endian 96-bit limbs.
+    fn u384(&mut self, section: &str) -> Result<String> {
+        let mut value = BigUint::ZERO;
+        for i in 0..4 {
+            value |= self.limb(section, 96)? << (96 * i);
+        }
+        Ok(format!("{value:#x}"))
+    }
+
+    /// Read a block of `len` felts of at most `bits` bits each.
+    fn block(&mut self, name: &'static str, len: usize, bits: u64) -> Result<HintBlock> {
+        let offset = self.offset;
+        for _ in 0..len {
+            self.limb(name, bits)?;
+        }
+        Ok(HintBlock {
+            name,
+            offset,
+            len,
+            items: None,
+        })
+    }
+
+    /// Read a span: a length prefix, then that many items of `item_len` felts.
+    fn span(&mut self, name: &'static str, item_len: usize, bits: u64) -> Result<HintBlock> {
+        let offset = self.offset;
+        let items = self.usize(name)?;
+        let remaining = self.calldata.len() - self.offset;
+        if items.saturating_mul(item_len) > remaining {
+            return Err(Error::InvalidCalldata(format!(
+                "span {name} at offset {offset} holds {items} items, but only {remaining} felts follow"
+            )));
+        }
+        self.block(name, items * item_len, bits)?;
+        Ok(HintBlock {
+            name,
+            offset,
+            len: self.offset - offset,
+            items: Some(items),
+        })
+    }
+
+    /// Read the blocks of a hint section.
+    fn section(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Result<Vec<HintBlock>>,
+    ) -> Result<HintSection> {
+        let offset = self.offset;
+        let blocks = read(self)?;
+        Ok(HintSection {
+            offset,
+            len: self.offset - offset,
+            blocks,
+        })
+    }
+
+    /// Read a G1 point.
+    fn g1(&mut self, name: &'static str) -> Result<G1Point> {
+        Ok(G1Point {
+            x: self.u384(&format!("{name}.x"))?,
+            y: self.u384(&format!("{name}.y"))?,
+        })
+    }
+}
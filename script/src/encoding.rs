@@ -0,0 +1,176 @@
+//! # Calldata Encoding
+//!
+//! Serialization of Garaga calldata into the formats consumed by Starknet tooling.
+//!
+//! Every format implements [`CalldataEncoder`], and [`CalldataEncoding`] selects one
+//! of the built-in encoders from the command line:
+//!
+//! | Encoding  | Output                                               | Consumer                  |
+//! |-----------|------------------------------------------------------|---------------------------|
+//! | `hex`     | One `0x`-prefixed value per line                     | snforge `read_txt`        |
+//! | `json`    | JSON array of decimal strings                        | Deployment scripts        |
+//! | `starkli` | Single line of `Array<felt252>` arguments            | `starkli invoke`          |
+//! | `sncast`  | Single line `--calldata` flag for `Array<felt252>`   | `sncast invoke`           |
+//! | `binary`  | Length-prefixed big-endian blob                      | Backend services          |
+
+use clap::ValueEnum;
+use num_bigint::BigUint;
+
+/// Serializes calldata into a specific output format.
+///
+/// Implement this trait to plug a custom format into [`crate::create_proof_fixture`].
+pub trait CalldataEncoder {
+    /// File extension (without the leading dot) used when writing encoded calldata.
+    fn file_extension(&self) -> &'static str;
+
+    /// Encode the calldata into the bytes written to disk.
+    fn encode(&self, calldata: &[BigUint]) -> Vec<u8>;
+}
+
+/// Built-in calldata encodings.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Default)]
+pub enum CalldataEncoding {
+    /// Newline-separated hex values for Starknet Foundry's `read_txt`.
+    #[default]
+    Hex,
+
+    /// JSON array of decimal strings.
+    Json,
+
+    /// Space-separated arguments for `starkli invoke`.
+    Starkli,
+
+    /// `--calldata` arguments for `sncast invoke`.
+    Sncast,
+
+    /// Compact length-prefixed binary blob.
+    Binary,
+}
+
+impl CalldataEncoding {
+    /// The encoder implementing this encoding.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use fibonacci_starknet::encoding::CalldataEncoding;
+    /// use num_bigint::BigUint;
+    ///
+    /// let calldata = vec![BigUint::from(255u32), BigUint::from(4095u32)];
+    /// let json = CalldataEncoding::Json.encoder().encode(&calldata);
+    /// assert_eq!(json, br#"["255","4095"]"#);
+    /// ```
+    pub fn encoder(self) -> &'static dyn CalldataEncoder {
+        match self {
+            CalldataEncoding::Hex => &HexLinesEncoder,
+            CalldataEncoding::Json => &JsonArrayEncoder,
+            CalldataEncoding::Starkli => &StarkliArgsEncoder,
+            CalldataEncoding::Sncast => &SncastArgsEncoder,
+            CalldataEncoding::Binary => &BinaryEncoder,
+        }
+    }
+}
+
+/// Newline-separated `0x`-prefixed hex values.
+///
+/// This is the format loaded by the Cairo tests through snforge's `read_txt`.
+pub struct HexLinesEncoder;
+
+impl CalldataEncoder for HexLinesEncoder {
+    fn file_extension(&self) -> &'static str {
+        "txt"
+    }
+
+    fn encode(&self, calldata: &[BigUint]) -> Vec<u8> {
+        crate::calldata::biguint_vec_to_hex_string(calldata).into_bytes()
+    }
+}
+
+/// JSON array of decimal strings, e.g. `["2040","11817..."]`.
+pub struct JsonArrayEncoder;
+
+impl CalldataEncoder for JsonArrayEncoder {
+    fn file_extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn encode(&self, calldata: &[BigUint]) -> Vec<u8> {
+        let values: Vec<String> = calldata.iter().map(BigUint::to_string).collect();
+        serde_json::to_vec(&values).expect("a list of strings always serializes")
+    }
+}
+
+/// Space-separated hex values serialized as an `Array<felt252>` argument.
+///
+/// `starkli invoke` takes raw felts, so the calldata is prefixed with its length to
+/// match the `proof: Array<felt252>` parameter of `verify_sp1_proof`:
+///
+/// ```bash
+/// starkli invoke <CONTRACT> verify_sp1_proof $(cat groth16-calldata.starkli)
+/// ```
+pub struct StarkliArgsEncoder;
+
+impl CalldataEncoder for StarkliArgsEncoder {
+    fn file_extension(&self) -> &'static str {
+        "starkli"
+    }
+
+    fn encode(&self, calldata: &[BigUint]) -> Vec<u8> {
+        (array_arguments(calldata) + "\n").into_bytes()
+    }
+}
+
+/// `--calldata` flag serialized as an `Array<felt252>` argument.
+///
+/// Like [`StarkliArgsEncoder`], the values are prefixed with the array length:
+///
+/// ```bash
+/// sncast invoke --contract-address <CONTRACT> --function verify_sp1_proof \
+///     $(cat groth16-calldata.sncast)
+/// ```
+pub struct SncastArgsEncoder;
+
+impl CalldataEncoder for SncastArgsEncoder {
+    fn file_extension(&self) -> &'static str {
+        "sncast"
+    }
+
+    fn encode(&self, calldata: &[BigUint]) -> Vec<u8> {
+        format!("--calldata {}\n", array_arguments(calldata)).into_bytes()
+    }
+}
+
+/// Compact length-prefixed binary encoding.
+///
+/// ## Layout
+///
+/// - Element count as a big-endian `u32`
+/// - For every element: its byte length as a `u8`, followed by the minimal
+///   big-endian representation of the value
+pub struct BinaryEncoder;
+
+impl CalldataEncoder for BinaryEncoder {
+    fn file_extension(&self) -> &'static str {
+        "bin"
+    }
+
+    fn encode(&self, calldata: &[BigUint]) -> Vec<u8> {
+        let count = u32::try_from(calldata.len()).expect("calldata length fits in a u32");
+        let mut bytes = count.to_be_bytes().to_vec();
+        for value in calldata {
+            let value_bytes = value.to_bytes_be();
+            let len = u8::try_from(value_bytes.len()).expect("calldata elements fit in 255 bytes");
+            bytes.push(len);
+            bytes.extend_from_slice(&value_bytes);
+        }
+        bytes
+    }
+}
+
+/// Serialize calldata as a length-prefixed `Array<felt252>` argument list.
+fn array_arguments(calldata: &[BigUint]) -> String {
+    std::iter::once(format!("0x{:x}", calldata.len()))
+        .chain(calldata.iter().map(|value| format!("0x{:x}", value)))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
@@ -0,0 +1,227 @@
+//! # Error Handling
+//!
+//! A single error type for the Starknet proof pipeline. Each variant belongs to a
+//! stage of the pipeline and maps to its own process exit code, so CI scripts can
+//! tell a failed prover run apart from, say, an unwritable fixture directory.
+//!
+//! | Exit code | Stage                        |
+//! |-----------|------------------------------|
+//! | 10        | Proof generation             |
+//! | 11        | Verification key decoding    |
+//! | 12        | Garaga calldata conversion   |
+//! | 13        | Fixture, proof and input I/O |
+//! | 14        | Off-chain verification       |
+//! | 15        | Calldata consistency check   |
+//! | 16        | Generated Cairo code check   |
+//! | 17        | Cycle budget checks          |
+//!
+//! See [`Error::exit_code`] for the mapping of each variant.
+
+use num_bigint::BigUint;
+use sp1_sdk::SP1VerificationError;
+use std::path::PathBuf;
+
+/// Result type used throughout the Starknet proof pipeline.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Errors that can occur while proving and converting proofs for Starknet.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The SP1 prover failed to generate a proof.
+    ///
+    /// This covers local provers running out of memory as well as
+    /// failures reported by the Succinct Prover Network.
+    #[error("proof generation failed: {0:#}")]
+    Prover(anyhow::Error),
+
+    /// Only compressed proofs can be wrapped into a Groth16 proof.
+    #[error("cannot wrap a {0} proof: only compressed proofs can be wrapped")]
+    NotWrappable(&'static str),
+
+    /// The program verification key hash could not be decoded.
+    #[error("invalid verification key hash `{vkey}`: {source}")]
+    VkeyDecode {
+        /// The `0x`-prefixed hash returned by `vk.bytes32()`.
+        vkey: String,
+        /// The underlying hex decoding error.
+        #[source]
+        source: hex::FromHexError,
+    },
+
+    /// Garaga rejected the proof while generating Starknet calldata.
+    #[error("Garaga calldata conversion failed: {0}")]
+    Garaga(String),
+
+    /// The proof type cannot be verified by the Garaga SP1 Verifier.
+    #[error("{0} proofs are not supported on Starknet: Garaga only verifies SP1 Groth16 proofs")]
+    UnsupportedOnStarknet(&'static str),
+
+    /// A calldata element does not fit in a felt252.
+    #[error("calldata element {index} (0x{value:x}) exceeds the felt252 modulus")]
+    FeltOutOfRange {
+        /// Position of the element in the calldata.
+        index: usize,
+        /// The offending value.
+        value: BigUint,
+    },
+
+    /// A fixture or generated file or directory could not be created or written.
+    #[error("fixture I/O error on {}: {source}", path.display())]
+    FixtureIo {
+        /// The file or directory being accessed.
+        path: PathBuf,
+        /// The underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The JSON fixture could not be serialized or parsed.
+    #[error("invalid fixture JSON: {0}")]
+    FixtureSerialization(#[from] serde_json::Error),
+
+    /// A program input file does not match the expected input type.
+    #[error("invalid program input {}: {message}", path.display())]
+    InvalidInput {
+        /// The JSON or TOML input file.
+        path: PathBuf,
+        /// The error reported by the JSON or TOML parser.
+        message: String,
+    },
+
+    /// A hex field of a JSON fixture could not be decoded.
+    #[error("invalid `{field}` in fixture: {source}")]
+    InvalidFixture {
+        /// Name of the offending fixture field.
+        field: &'static str,
+        /// The underlying hex decoding error.
+        #[source]
+        source: hex::FromHexError,
+    },
+
+    /// A calldata file does not follow the Garaga SP1 Groth16 layout.
+    #[error("invalid calldata: {0}")]
+    InvalidCalldata(String),
+
+    /// A calldata section does not match the fixture it was generated from.
+    #[error(
+        "calldata `{section}` does not match the fixture: expected {expected}, found {actual}"
+    )]
+    CalldataMismatch {
+        /// Name of the mismatching section, e.g. `vkey`.
+        section: &'static str,
+        /// The value derived from the fixture.
+        expected: String,
+        /// The value found in the calldata.
+        actual: String,
+    },
+
+    /// The generated `SP1_PROGRAM` Cairo module does not match the current program.
+    #[error(
+        "{} is out of date: expected SP1_PROGRAM = {expected}, found {}",
+        path.display(),
+        found.as_deref().unwrap_or("no generated constant")
+    )]
+    StaleProgramVkey {
+        /// The generated Cairo module.
+        path: PathBuf,
+        /// The verification key hash of the current ELF.
+        expected: String,
+        /// The checked-in value, if the file could be read and parsed.
+        found: Option<String>,
+    },
+
+    /// The programs passed to the registry are inconsistent, e.g. duplicated.
+    #[error("invalid program registry: {0}")]
+    InvalidRegistry(String),
+
+    /// A `SP1ProofWithPublicValues` could not be saved or loaded.
+    #[error("proof file error on {}: {source:#}", path.display())]
+    ProofIo {
+        /// The proof file.
+        path: PathBuf,
+        /// The error reported by the SP1 SDK.
+        source: anyhow::Error,
+    },
+
+    /// A binary artifact, such as `vk.bin`, could not be encoded or decoded.
+    #[error("invalid binary artifact {}: {source}", path.display())]
+    Bincode {
+        /// The artifact file.
+        path: PathBuf,
+        /// The underlying bincode error.
+        #[source]
+        source: bincode::Error,
+    },
+
+    /// The cycle baseline of `fibonacci bench` has not been recorded.
+    #[error(
+        "no cycle baseline at {}: record one with `cargo run --release -- bench --save-baseline` \
+         and check it in",
+        path.display()
+    )]
+    MissingCycleBaseline {
+        /// The expected baseline file.
+        path: PathBuf,
+    },
+
+    /// Cycle counts of the program grew past the allowed increase over the baseline.
+    #[error(
+        "{regressions} cycle count(s) grew by more than {max_increase_percent}% over the baseline"
+    )]
+    CycleRegression {
+        /// Number of regressed totals and regions.
+        regressions: usize,
+        /// The allowed increase, in percent.
+        max_increase_percent: f64,
+    },
+
+    /// The program runs for more cycles than allowed for proving without `--force`.
+    #[error(
+        "the program executes at least {cycles} cycles, more than the {max_cycles} allowed for \
+         proving (pass --force to prove anyway)"
+    )]
+    ProvingBudgetExceeded {
+        /// Number of cycles of the pre-flight execution, or of its cap if it
+        /// was aborted.
+        cycles: u64,
+        /// The allowed number of cycles.
+        max_cycles: u64,
+    },
+
+    /// The SP1 SDK rejected a proof during off-chain verification.
+    #[error("proof verification failed: {0}")]
+    Verification(#[from] SP1VerificationError),
+}
+
+impl Error {
+    /// The process exit code reported for this error.
+    ///
+    /// Codes start at 10 to stay clear of the generic failure code (1) and
+    /// clap's usage error code (2).
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::Prover(_) | Error::NotWrappable(_) => 10,
+            Error::VkeyDecode { .. } => 11,
+            Error::Garaga(_) | Error::FeltOutOfRange { .. } | Error::UnsupportedOnStarknet(_) => 12,
+            Error::FixtureIo { .. }
+            | Error::FixtureSerialization(_)
+            | Error::InvalidInput { .. }
+            | Error::InvalidFixture { .. }
+            | Error::InvalidCalldata(_)
+            | Error::InvalidRegistry(_)
+            | Error::ProofIo { .. }
+            | Error::Bincode { .. }
+            | Error::MissingCycleBaseline { .. } => 13,
+            Error::Verification(_) => 14,
+            Error::CalldataMismatch { .. } => 15,
+            Error::StaleProgramVkey { .. } => 16,
+            Error::CycleRegression { .. } | Error::ProvingBudgetExceeded { .. } => 17,
+        }
+    }
+
+    /// Helper for attaching the offending path to an I/O error.
+    pub(crate) fn fixture_io(path: impl Into<PathBuf>) -> impl FnOnce(std::io::Error) -> Self {
+        let path = path.into();
+        move |source| Error::FixtureIo { path, source }
+    }
+}
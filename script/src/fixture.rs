@@ -0,0 +1,490 @@
+//! # Proof Fixtures
+//!
+//! Test fixtures containing SP1 proof data for the Cairo verification contract,
+//! together with the Starknet calldata derived from them.
+
+use crate::artifacts::{save_proof_artifacts, ProofArtifacts};
+use crate::calldata::{
+    biguint_vec_to_felts, get_sp1_garaga_starknet_calldata, sp1_groth16_calldata, CalldataFormat,
+};
+use crate::decode::check_calldata_consistency;
+use crate::encoding::CalldataEncoding;
+use crate::error::{Error, Result};
+use crate::mock::{mock_groth16_calldata, mock_groth16_proof_bytes};
+use clap::ValueEnum;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{HashableKey, SP1Proof, SP1ProofWithPublicValues, SP1VerifyingKey};
+use std::path::{Path, PathBuf};
+
+/// Supported proof systems for Starknet verification.
+///
+/// Each proof system has different characteristics:
+/// - **Groth16**: Constant-size proofs, fast verification, requires trusted setup
+/// - **PLONK**: Universal setup, larger proofs; not verifiable on Starknet yet
+/// - **Core** / **Compressed**: Fast STARK proofs for local iteration; not
+///   verifiable on-chain until wrapped (see [`crate::wrap`])
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum ProofSystem {
+    /// Groth16 zero-knowledge proof system.
+    ///
+    /// Groth16 is currently the most practical choice for on-chain verification because:
+    /// - Constant proof size (~200 bytes)
+    /// - Fast verification time
+    /// - Well-supported by Garaga library
+    /// - Efficient gas costs on Starknet
+    Groth16,
+
+    /// PLONK zero-knowledge proof system.
+    ///
+    /// PLONK proofs rely on a universal setup rather than a circuit-specific one.
+    /// Garaga does not provide an SP1 PLONK verifier, so these proofs produce a
+    /// fixture but no Starknet calldata.
+    Plonk,
+
+    /// Core STARK proof, one shard proof per execution shard.
+    ///
+    /// The fastest proof to generate, useful to check that the program proves
+    /// at all. Its size grows with the execution, and it cannot be wrapped.
+    Core,
+
+    /// Compressed STARK proof, recursively reduced to a constant size.
+    ///
+    /// Skips the expensive SNARK wrapping step during development. A saved
+    /// compressed proof can later be lifted to Groth16 with `convert --wrap`.
+    Compressed,
+}
+
+impl ProofSystem {
+    /// Lowercase name of the proof system, as used in fixture file names.
+    pub fn name(self) -> &'static str {
+        match self {
+            ProofSystem::Groth16 => "groth16",
+            ProofSystem::Plonk => "plonk",
+            ProofSystem::Core => "core",
+            ProofSystem::Compressed => "compressed",
+        }
+    }
+
+    /// The proof system a proof was generated with.
+    pub fn of_proof(proof: &SP1Proof) -> Self {
+        match proof {
+            SP1Proof::Groth16(_) => ProofSystem::Groth16,
+            SP1Proof::Plonk(_) => ProofSystem::Plonk,
+            SP1Proof::Core(_) => ProofSystem::Core,
+            SP1Proof::Compressed(_) => ProofSystem::Compressed,
+        }
+    }
+
+    /// Whether Garaga can turn proofs of this system into Starknet calldata.
+    pub fn has_starknet_calldata(self) -> bool {
+        match self {
+            ProofSystem::Groth16 => true,
+            ProofSystem::Plonk | ProofSystem::Core | ProofSystem::Compressed => false,
+        }
+    }
+
+    /// Whether proofs of this system are SNARKs that an on-chain verifier can check.
+    ///
+    /// Core and compressed proofs are STARKs meant for local iteration; they must
+    /// be wrapped before any chain can verify them.
+    pub fn is_onchain_verifiable(self) -> bool {
+        match self {
+            ProofSystem::Groth16 | ProofSystem::Plonk => true,
+            ProofSystem::Core | ProofSystem::Compressed => false,
+        }
+    }
+}
+
+/// Test fixture containing SP1 proof data for contract testing.
+///
+/// This structure contains all the necessary data to test SP1 proof verification
+/// in Cairo contracts. It includes both the raw proof data and metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SP1FibonacciProofFixture {
+    /// The verification key for the SP1 program (hex string).
+    ///
+    /// This key uniquely identifies the SP1 program and must match
+    /// the `SP1_PROGRAM` constant in the verification contract.
+    pub vkey: String,
+
+    /// The public values committed by the SP1 program (hex string).
+    ///
+    /// These are the publicly verifiable outputs of the computation,
+    /// encoded in a format suitable for on-chain verification.
+    pub public_values: String,
+
+    /// The complete proof data (hex string).
+    ///
+    /// This contains the cryptographic proof that can be verified
+    /// to confirm the computation was performed correctly.
+    ///
+    /// Empty (`0x`) for core and compressed proofs, which have no compact
+    /// encoding; the full proof is saved in `{name}-proof.bin` instead.
+    pub proof: String,
+
+    /// Whether the proof can be verified on-chain.
+    ///
+    /// `false` for core and compressed proofs, which must be wrapped into a
+    /// Groth16 proof first. Fixtures written before this field existed only
+    /// contain on-chain verifiable proofs, hence the default.
+    #[serde(default = "onchain_verifiable_default")]
+    pub onchain_verifiable: bool,
+
+    /// Whether this is a mock fixture generated without proving.
+    ///
+    /// Mock fixtures carry the real verification key and public values, but a
+    /// dummy proof and calldata (see [`crate::mock`]) that no verifier accepts.
+    #[serde(default)]
+    pub mock: bool,
+}
+
+fn onchain_verifiable_default() -> bool {
+    true
+}
+
+impl SP1FibonacciProofFixture {
+    /// Load a JSON fixture, such as `groth16-fixture.json`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(Error::fixture_io(path))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// The public values committed by the program, as raw bytes.
+    pub fn public_values_bytes(&self) -> Result<Vec<u8>> {
+        decode_hex_field("public_values", &self.public_values)
+    }
+
+    /// The encoded proof, as raw bytes.
+    pub fn proof_bytes(&self) -> Result<Vec<u8>> {
+        decode_hex_field("proof", &self.proof)
+    }
+
+    /// Regenerate the Starknet calldata from the fixture contents.
+    ///
+    /// This produces the same calldata as [`get_sp1_garaga_starknet_calldata`]
+    /// without needing the original proof or verification key objects. Mock
+    /// fixtures get mock calldata again.
+    pub fn starknet_calldata(&self) -> Result<Vec<BigUint>> {
+        if self.mock {
+            return mock_groth16_calldata(&self.vkey, &self.public_values_bytes()?);
+        }
+
+        sp1_groth16_calldata(
+            &self.vkey,
+            &self.public_values_bytes()?,
+            &self.proof_bytes()?,
+        )
+    }
+}
+
+/// Decode a `0x`-prefixed hex field of a fixture.
+fn decode_hex_field(field: &'static str, value: &str) -> Result<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|source| Error::InvalidFixture { field, source })
+}
+
+/// Options controlling where and how [`create_proof_fixture`] writes its output.
+#[derive(Debug, Clone)]
+pub struct FixtureOptions {
+    /// Directory the fixture files are written to, or `None` to skip writing files.
+    pub out_dir: Option<PathBuf>,
+
+    /// Base name of the fixture files (`{name}-fixture.json`, `{name}-calldata.{ext}`).
+    ///
+    /// Defaults to the lowercase proof system name, e.g. `groth16`.
+    pub name: Option<String>,
+
+    /// The calldata representation (see [`CalldataFormat`]).
+    pub format: CalldataFormat,
+
+    /// The calldata file format (see [`CalldataEncoding`]).
+    pub encoding: CalldataEncoding,
+}
+
+impl FixtureOptions {
+    /// The base file name used for a proof of the given system.
+    pub fn file_name(&self, system: ProofSystem) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| system.name().to_string())
+    }
+}
+
+impl Default for FixtureOptions {
+    fn default() -> Self {
+        Self {
+            out_dir: Some(default_fixture_dir()),
+            name: None,
+            format: CalldataFormat::default(),
+            encoding: CalldataEncoding::default(),
+        }
+    }
+}
+
+/// The fixture directory of the Cairo contracts in this repository.
+///
+/// This path is resolved at compile time, so it only makes sense when running
+/// from a checkout of the repository. Installed binaries should pass an explicit
+/// output directory instead.
+pub fn default_fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../contracts/src/fixtures")
+}
+
+/// Result of [`create_proof_fixture`].
+#[derive(Debug, Clone)]
+pub struct FixtureOutput {
+    /// The fixture describing the proof.
+    pub fixture: SP1FibonacciProofFixture,
+    /// Number of calldata elements generated by Garaga.
+    ///
+    /// `None` when the proof system has no Starknet verifier (see
+    /// [`ProofSystem::has_starknet_calldata`]); no calldata file is written then.
+    pub calldata_len: Option<usize>,
+    /// The files written to disk, if any.
+    pub files: Option<FixtureFiles>,
+}
+
+/// Paths of the files written by [`create_proof_fixture`].
+#[derive(Debug, Clone)]
+pub struct FixtureFiles {
+    /// Directory the files were written to.
+    pub dir: PathBuf,
+    /// Path of the JSON fixture (`{name}-fixture.json`).
+    pub json_path: PathBuf,
+    /// Path of the calldata file (`{name}-calldata.{ext}`), if calldata was generated.
+    pub calldata_path: Option<PathBuf>,
+    /// The binary proof and verification key, when the proof itself was available.
+    pub artifacts: Option<ProofArtifacts>,
+}
+
+/// Create comprehensive test fixtures for the generated proof.
+///
+/// This function generates all the necessary files for testing SP1 proof verification
+/// in Cairo contracts. It creates both human-readable JSON fixtures and
+/// machine-readable calldata files.
+///
+/// ## Generated Files
+///
+/// 1. **JSON Fixture** (`{name}-fixture.json`):
+///    - Complete proof metadata
+///    - Verification key
+///    - Public values
+///    - Raw proof data
+///
+/// 2. **Calldata File** (`{name}-calldata.{ext}`, Starknet-verifiable systems only):
+///    - Formatted calldata for Starknet contracts
+///    - Encoded with the selected [`CalldataEncoding`] (`.txt` for the
+///      Starknet Foundry test framework by default)
+///    - Ready for direct use in contract calls
+///
+/// 3. **Binary Artifacts** (`{name}-proof.bin`, `vk.bin`):
+///    - The full `SP1ProofWithPublicValues` and `SP1VerifyingKey`
+///    - Used to verify the proof off-chain or re-wrap it later
+///
+/// ## Parameters
+///
+/// - `proof`: The generated SP1 proof with public values
+/// - `vk`: The SP1 verification key
+/// - `system`: The proof system used (default file name); must match `proof`
+/// - `options`: Output location, file naming and calldata format
+///
+/// ## Output Location
+///
+/// Files are saved to [`FixtureOptions::out_dir`], which defaults to
+/// `../contracts/src/fixtures/` relative to the script directory. No files are
+/// written when it is `None`; the fixture and calldata length are still returned.
+///
+/// ## Errors
+///
+/// Propagates calldata conversion errors and returns [`Error::FixtureIo`] if the
+/// fixture directory or files cannot be written. Nothing is written if the
+/// calldata does not commit to `vk` and the proof's public values
+/// ([`Error::CalldataMismatch`], see [`check_calldata_consistency`]). Proof
+/// systems without a Starknet verifier are not an error here: the fixture is
+/// written and [`FixtureOutput::calldata_len`] is `None`.
+pub fn create_proof_fixture(
+    proof: &SP1ProofWithPublicValues,
+    vk: &SP1VerifyingKey,
+    system: ProofSystem,
+    options: &FixtureOptions,
+) -> Result<FixtureOutput> {
+    // Generate Starknet-compatible calldata using Garaga
+    // This also rejects proofs that do not match the requested system
+    let calldata = if system.has_starknet_calldata() {
+        Some(get_sp1_garaga_starknet_calldata(proof, vk)?)
+    } else {
+        None
+    };
+
+    // Extract the public values from the proof
+    let bytes = proof.public_values.as_slice();
+
+    // Make sure the calldata was built for this program and these public values
+    if let Some(calldata) = &calldata {
+        check_calldata_consistency(calldata, &vk.bytes32(), bytes)?;
+    }
+
+    // STARK proofs have no compact encoding (`proof.bytes()` panics for them)
+    let proof_bytes = if system.is_onchain_verifiable() {
+        proof.bytes()
+    } else {
+        Vec::new()
+    };
+
+    // Create a comprehensive test fixture with all proof data
+    let fixture = SP1FibonacciProofFixture {
+        vkey: vk.bytes32().to_string(),
+        public_values: format!("0x{}", hex::encode(bytes)),
+        proof: format!("0x{}", hex::encode(proof_bytes)),
+        onchain_verifiable: system.is_onchain_verifiable(),
+        mock: false,
+    };
+
+    let mut output = finish_fixture(fixture, calldata, system, options)?;
+
+    // Keep the full proof and verification key next to the fixture
+    if let Some(files) = &mut output.files {
+        files.artifacts = Some(save_proof_artifacts(
+            proof,
+            vk,
+            &files.dir,
+            &options.file_name(system),
+        )?);
+    }
+
+    Ok(output)
+}
+
+/// Create fixtures for a program run without generating a proof.
+///
+/// The fixture is marked `"mock": true` and not on-chain verifiable. Its calldata
+/// comes from [`mock_groth16_calldata`]: it has the length and layout of real
+/// Garaga calldata, with the real verification key and public values, so the
+/// rest of the pipeline can be exercised offline in seconds.
+///
+/// ## Parameters
+///
+/// - `vk`: The SP1 verification key of the program
+/// - `public_values`: The public values of an execution of the program
+/// - `system`: The proof system to mock (default file name)
+/// - `options`: Output location, file naming and calldata format
+///
+/// ## Errors
+///
+/// Returns the same errors as [`create_proof_fixture`].
+pub fn create_mock_fixture(
+    vk: &SP1VerifyingKey,
+    public_values: &[u8],
+    system: ProofSystem,
+    options: &FixtureOptions,
+) -> Result<FixtureOutput> {
+    let vkey = vk.bytes32().to_string();
+
+    let (proof_bytes, calldata) = if system.has_starknet_calldata() {
+        let calldata = mock_groth16_calldata(&vkey, public_values)?;
+        (mock_groth16_proof_bytes(), Some(calldata))
+    } else {
+        (Vec::new(), None)
+    };
+
+    let fixture = SP1FibonacciProofFixture {
+        vkey,
+        public_values: format!("0x{}", hex::encode(public_values)),
+        proof: format!("0x{}", hex::encode(proof_bytes)),
+        onchain_verifiable: false,
+        mock: true,
+    };
+
+    finish_fixture(fixture, calldata, system, options)
+}
+
+/// Regenerate calldata and fixture files from an existing fixture.
+///
+/// This is the counterpart of [`create_proof_fixture`] for proofs that were
+/// generated earlier: the calldata is rebuilt from the fixture's hex fields, so
+/// output formats can be changed without generating a new proof.
+///
+/// ## Errors
+///
+/// Returns [`Error::InvalidFixture`] if a fixture field is not valid hex, and
+/// otherwise the same errors as [`create_proof_fixture`].
+pub fn convert_fixture(
+    fixture: SP1FibonacciProofFixture,
+    system: ProofSystem,
+    options: &FixtureOptions,
+) -> Result<FixtureOutput> {
+    let calldata = if system.has_starknet_calldata() {
+        let calldata = fixture.starknet_calldata()?;
+        check_calldata_consistency(&calldata, &fixture.vkey, &fixture.public_values_bytes()?)?;
+        Some(calldata)
+    } else {
+        None
+    };
+    finish_fixture(fixture, calldata, system, options)
+}
+
+/// Validate the calldata and write the fixture files requested by `options`.
+fn finish_fixture(
+    fixture: SP1FibonacciProofFixture,
+    calldata: Option<Vec<BigUint>>,
+    system: ProofSystem,
+    options: &FixtureOptions,
+) -> Result<FixtureOutput> {
+    if let (Some(calldata), CalldataFormat::Felt) = (&calldata, options.format) {
+        biguint_vec_to_felts(calldata)?;
+    }
+
+    let files = match &options.out_dir {
+        Some(out_dir) => Some(write_fixture_files(
+            out_dir,
+            &options.file_name(system),
+            &fixture,
+            calldata.as_deref(),
+            options.encoding,
+        )?),
+        None => None,
+    };
+
+    Ok(FixtureOutput {
+        fixture,
+        calldata_len: calldata.as_ref().map(Vec::len),
+        files,
+    })
+}
+
+/// Write the JSON fixture and encoded calldata to `out_dir`.
+fn write_fixture_files(
+    out_dir: &Path,
+    name: &str,
+    fixture: &SP1FibonacciProofFixture,
+    calldata: Option<&[BigUint]>,
+    encoding: CalldataEncoding,
+) -> Result<FixtureFiles> {
+    std::fs::create_dir_all(out_dir).map_err(Error::fixture_io(out_dir))?;
+
+    // Save the JSON fixture with complete proof metadata
+    let json_path = out_dir.join(format!("{name}-fixture.json"));
+    std::fs::write(&json_path, serde_json::to_string_pretty(fixture)?)
+        .map_err(Error::fixture_io(&json_path))?;
+
+    // Save the encoded calldata for tests and deployment tooling
+    let calldata_path = match calldata {
+        Some(calldata) => {
+            let encoder = encoding.encoder();
+            let path = out_dir.join(format!("{name}-calldata.{}", encoder.file_extension()));
+            std::fs::write(&path, encoder.encode(calldata)).map_err(Error::fixture_io(&path))?;
+            Some(path)
+        }
+        None => None,
+    };
+
+    Ok(FixtureFiles {
+        dir: out_dir.to_path_buf(),
+        json_path,
+        calldata_path,
+        artifacts: None,
+    })
+}
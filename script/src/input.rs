@@ -0,0 +1,103 @@
+//! # Program Inputs
+//!
+//! The data written to the `SP1Stdin` of a guest program.
+//!
+//! Inputs are serde-typed structs defined in `fibonacci-lib`, such as
+//! [`FibonacciInput`], read from JSON or TOML documents and written with
+//! `SP1Stdin::write`. Programs that read raw bytes instead can be given a file
+//! that is written as-is with `SP1Stdin::write_slice`.
+
+use crate::error::{Error, Result};
+use fibonacci_lib::FibonacciInput;
+use serde::de::DeserializeOwned;
+use serde::{Serialize, Serializer};
+use sp1_sdk::SP1Stdin;
+use std::path::Path;
+
+/// The input passed to a guest program through `SP1Stdin`.
+///
+/// Serializes as the typed input (`{ "n": 10 }`) or as a `0x`-prefixed hex
+/// string for raw bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum ProgramInput {
+    /// A typed input of the Fibonacci program, written with `SP1Stdin::write`.
+    Fibonacci(FibonacciInput),
+    /// Raw bytes, written with `SP1Stdin::write_slice`.
+    Bytes(#[serde(serialize_with = "serialize_hex")] Vec<u8>),
+}
+
+impl ProgramInput {
+    /// The input selected by the `--n`, `--input` and `--input-bytes` options.
+    ///
+    /// `--input` and `--input-bytes` take precedence over `--n`, which is always
+    /// set through its default value.
+    pub fn from_args(n: u32, input: Option<&Path>, input_bytes: Option<&Path>) -> Result<Self> {
+        if let Some(path) = input_bytes {
+            let bytes = std::fs::read(path).map_err(Error::fixture_io(path))?;
+            return Ok(Self::Bytes(bytes));
+        }
+        match input {
+            Some(path) => Ok(Self::Fibonacci(read_input_file(path)?)),
+            None => Ok(Self::Fibonacci(FibonacciInput { n })),
+        }
+    }
+
+    /// The typed Fibonacci input, if this is not a raw byte input.
+    pub fn fibonacci(&self) -> Option<FibonacciInput> {
+        match self {
+            Self::Fibonacci(input) => Some(*input),
+            Self::Bytes(_) => None,
+        }
+    }
+
+    /// Build the `SP1Stdin` holding this input.
+    pub fn to_stdin(&self) -> SP1Stdin {
+        let mut stdin = SP1Stdin::new();
+        match self {
+            Self::Fibonacci(input) => stdin.write(input),
+            Self::Bytes(bytes) => stdin.write_slice(bytes),
+        }
+        stdin
+    }
+
+    /// A one-line description of the input for progress messages.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Fibonacci(input) => format!("n = {}", input.n),
+            Self::Bytes(bytes) => format!("{} raw bytes", bytes.len()),
+        }
+    }
+}
+
+/// Serialize raw input bytes as a `0x`-prefixed hex string.
+fn serialize_hex<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+}
+
+/// Read a typed input from a JSON or TOML document.
+///
+/// Files with a `.toml` extension are parsed as TOML, everything else as JSON.
+///
+/// ## Errors
+///
+/// Returns [`Error::FixtureIo`] if the file cannot be read, and
+/// [`Error::InvalidInput`] if it does not match the shape of `T`.
+pub fn read_input_file<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).map_err(Error::fixture_io(path))?;
+
+    let is_toml = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("toml"));
+    let parsed = if is_toml {
+        toml::from_str(&text).map_err(|error| error.to_string())
+    } else {
+        serde_json::from_str(&text).map_err(|error| error.to_string())
+    };
+
+    parsed.map_err(|message| Error::InvalidInput {
+        path: path.to_path_buf(),
+        message,
+    })
+}
@@ -0,0 +1,90 @@
+//! # Fibonacci Starknet
+//!
+//! Reusable building blocks for turning SP1 Fibonacci proofs into data that can be
+//! verified on Starknet through the Garaga SP1 Verifier.
+//!
+//! The `fibonacci`, `starknet` and `vkey` binaries are thin command-line wrappers
+//! around this library. Backend services can depend on it directly to:
+//!
+//! - Access the compiled guest program ([`FIBONACCI_ELF`])
+//! - Generate the Cairo `SP1_PROGRAM` constant of the verification contract ([`cairo`]),
+//!   or a registry of several programs ([`registry`])
+//! - Convert SP1 Groth16 proofs into Starknet calldata ([`get_sp1_garaga_starknet_calldata`],
+//!   or [`get_sp1_garaga_starknet_calldata_felt`] for range-checked felt252 values)
+//! - Format calldata for Starknet Foundry tests ([`biguint_vec_to_hex_string`]) or
+//!   deployment tooling ([`CalldataEncoding`])
+//! - Write proof fixtures for contract testing ([`create_proof_fixture`]), or rebuild
+//!   them from a saved fixture without proving again ([`convert_fixture`])
+//! - Prove many inputs with a single setup and summarize them in a manifest ([`batch`])
+//! - Skip proving when the same program, input and proof system were proven before ([`cache`])
+//! - Decode calldata back into proof points, vkey and public inputs, and check it
+//!   against its fixture ([`decode`])
+//! - Build the program stdin from typed JSON/TOML inputs or raw bytes ([`input`])
+//! - Save and reload the full proof and verification key ([`artifacts`])
+//! - Produce mock fixtures without proving, for offline CI ([`create_mock_fixture`])
+//! - Lift compressed proofs from fast local iteration to Groth16 ([`wrap`])
+//! - Break execution cycles down by syscall, opcode and `cycle-tracker` section, and
+//!   export the section totals in folded format ([`profile`])
+//! - Cap execution cycles and refuse to start oversized SNARK proofs ([`preflight`])
+//! - Gate guest changes on a cycle budget, compared against a checked-in baseline ([`bench`])
+//! - Summarize a proving session as a machine-readable JSON report ([`report`])
+//!
+//! Failures are reported through [`Error`], which maps every pipeline stage to a
+//! distinct process exit code.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use fibonacci_lib::FibonacciInput;
+//! use fibonacci_starknet::input::ProgramInput;
+//! use fibonacci_starknet::{get_sp1_garaga_starknet_calldata, FIBONACCI_ELF};
+//! use sp1_sdk::ProverClient;
+//!
+//! let client = ProverClient::from_env();
+//! let (pk, vk) = client.setup(FIBONACCI_ELF);
+//!
+//! let stdin = ProgramInput::Fibonacci(FibonacciInput { n: 10 }).to_stdin();
+//!
+//! let proof = client.prove(&pk, &stdin).groth16().run()?;
+//! let calldata = get_sp1_garaga_starknet_calldata(&proof, &vk)?;
+//! ```
+
+use sp1_sdk::include_elf;
+
+pub mod artifacts;
+pub mod batch;
+pub mod bench;
+pub mod cache;
+pub mod cairo;
+pub mod calldata;
+pub mod console;
+pub mod decode;
+pub mod encoding;
+pub mod error;
+pub mod fixture;
+pub mod input;
+pub mod mock;
+pub mod preflight;
+pub mod profile;
+pub mod program;
+pub mod registry;
+pub mod report;
+pub mod wrap;
+
+pub use calldata::{
+    biguint_vec_to_hex_string, get_sp1_garaga_starknet_calldata,
+    get_sp1_garaga_starknet_calldata_felt, CalldataFormat,
+};
+pub use encoding::{CalldataEncoder, CalldataEncoding};
+pub use error::{Error, Result};
+pub use fixture::{
+    convert_fixture, create_mock_fixture, create_proof_fixture, FixtureOptions, FixtureOutput,
+    ProofSystem, SP1FibonacciProofFixture,
+};
+
+/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
+///
+/// This binary contains the compiled SP1 Fibonacci program that will be executed
+/// within the zero-knowledge virtual machine. The program is embedded at compile
+/// time using the `include_elf!` macro and shared by every binary in this crate.
+pub const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci-program");
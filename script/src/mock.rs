@@ -0,0 +1,145 @@
+//! # Mock Proofs
+//!
+//! Deterministic stand-ins for SP1 Groth16 proofs and their Starknet calldata.
+//!
+//! Mock fixtures let offline CI exercise the whole script-to-fixture pipeline in
+//! seconds. The calldata has the exact length and layout produced by Garaga for a
+//! real proof, and carries the real program verification key and public values,
+//! but the proof points are the BN254 generators and every hint is zero, apart
+//! from the length prefixes of its spans. It is rejected by the on-chain verifier.
+//!
+//! ## Calldata Layout
+//!
+//! | Section       | Felts              | Mock contents                      |
+//! |---------------|--------------------|------------------------------------|
+//! | Span length   | 1                  | Number of felts that follow        |
+//! | A (G1)        | 8                  | BN254 G1 generator                 |
+//! | B (G2)        | 16                 | BN254 G2 generator                 |
+//! | C (G1)        | 8                  | BN254 G1 generator                 |
+//! | Program vkey  | 2                  | `vk.bytes32()` as a `u256`         |
+//! | Public values | 1 + 8 per word     | Real public values, as `u32` limbs |
+//! | Hints         | [`MOCK_HINTS_LEN`] | Zeros, in Garaga's hint layout     |
+
+use crate::calldata::{decode_vkey_hash, SP1_GROTH16_PROOF_LEN};
+use crate::decode::{E12D_LEN, SCALING_FACTOR_LEN, SMALL_Q_LEN, U288_LEN};
+use crate::error::Result;
+use num_bigint::BigUint;
+
+/// Number of hint felts (pairing check and MSM hints) in SP1 Groth16 calldata.
+pub const MOCK_HINTS_LEN: usize = 1981;
+
+/// Number of `ris` Miller loop results in the pairing check hints of a real proof.
+const MOCK_RIS_COUNT: usize = 35;
+/// Number of `big_q` coefficients in the pairing check hints of a real proof.
+const MOCK_BIG_Q_COUNT: usize = 190;
+/// Number of felts of the MSM hint of a real proof.
+const MOCK_MSM_HINT_LEN: usize = 24;
+
+/// x coordinate of the BN254 G1 generator.
+const G1_X: &str = "1";
+/// y coordinate of the BN254 G1 generator.
+const G1_Y: &str = "2";
+
+/// Coordinates of the BN254 G2 generator, in Garaga order (x0, x1, y0, y1).
+const G2: [&str; 4] = [
+    "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+    "11559732032986387107991004021392285783925812861821192530917403151452391805634",
+    "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+    "4082367875863433681332203403145435568316851327593401208105741076214120093531",
+];
+
+/// Build mock Starknet calldata for a program and its public values.
+///
+/// ## Parameters
+///
+/// - `vkey_hash`: The `0x`-prefixed program verification key hash (`vk.bytes32()`)
+/// - `public_values`: The public values committed by the program
+///
+/// ## Errors
+///
+/// Returns [`crate::Error::VkeyDecode`] if the verification key hash is not valid hex.
+///
+/// ## Example
+///
+/// ```rust
+/// use fibonacci_starknet::mock::mock_groth16_calldata;
+///
+/// let vkey = format!("0x{}", "11".repeat(32));
+/// let calldata = mock_groth16_calldata(&vkey, &[0u8; 96]).unwrap();
+///
+/// // Same length as the calldata of a real Fibonacci proof
+/// assert_eq!(calldata.len(), 2041);
+/// assert_eq!(calldata[0], (calldata.len() - 1).into());
+/// ```
+pub fn mock_groth16_calldata(vkey_hash: &str, public_values: &[u8]) -> Result<Vec<BigUint>> {
+    let vkey = decode_vkey_hash(vkey_hash)?;
+
+    let mut calldata = Vec::new();
+
+    // Proof points A, B and C as u384 limbs
+    for coordinate in [G1_X, G1_Y].iter().chain(&G2).chain(&[G1_X, G1_Y]) {
+        calldata.extend(u384_limbs(&decimal(coordinate)));
+    }
+
+    // Program verification key as a u256 (low, high)
+    let vkey = BigUint::from_bytes_be(&vkey);
+    let low_mask = (BigUint::from(1u32) << 128) - 1u32;
+    calldata.push(&vkey & &low_mask);
+    calldata.push(vkey >> 128);
+
+    // Public values, one 32-byte word at a time, as big-endian u32 limbs
+    let words = public_values.chunks(32);
+    calldata.push(words.len().into());
+    for word in words {
+        let mut padded = [0u8; 32];
+        padded[..word.len()].copy_from_slice(word);
+        calldata.extend(padded.chunks(4).map(BigUint::from_bytes_be));
+    }
+
+    // Pairing check and MSM hints
+    let hints_offset = calldata.len();
+    let zeros = |calldata: &mut Vec<BigUint>, len| {
+        calldata.extend(std::iter::repeat_n(BigUint::ZERO, len));
+    };
+    zeros(&mut calldata, 2 * E12D_LEN + SCALING_FACTOR_LEN);
+    calldata.push(MOCK_RIS_COUNT.into());
+    zeros(&mut calldata, MOCK_RIS_COUNT * E12D_LEN);
+    calldata.push(MOCK_BIG_Q_COUNT.into());
+    zeros(&mut calldata, MOCK_BIG_Q_COUNT * U288_LEN);
+    zeros(&mut calldata, 1 + SMALL_Q_LEN);
+    calldata.push(MOCK_MSM_HINT_LEN.into());
+    zeros(&mut calldata, MOCK_MSM_HINT_LEN);
+    debug_assert_eq!(calldata.len() - hints_offset, MOCK_HINTS_LEN);
+
+    // Span length prefix
+    calldata.insert(0, calldata.len().into());
+
+    Ok(calldata)
+}
+
+/// Encoded mock SP1 Groth16 proof, the counterpart of `proof.bytes()`.
+///
+/// A zero verifier selector followed by the generator points in SP1 order
+/// (A.x, A.y, B.x1, B.x0, B.y1, B.y0, C.x, C.y).
+pub fn mock_groth16_proof_bytes() -> Vec<u8> {
+    let [x0, x1, y0, y1] = G2;
+    let mut bytes = vec![0u8; 4];
+    for coordinate in [G1_X, G1_Y, x1, x0, y1, y0, G1_X, G1_Y] {
+        let value = decimal(coordinate).to_bytes_be();
+        bytes.extend(std::iter::repeat_n(0u8, 32 - value.len()));
+        bytes.extend(value);
+    }
+    debug_assert_eq!(bytes.len(), SP1_GROTH16_PROOF_LEN);
+    bytes
+}
+
+/// Parse one of the decimal constants above.
+fn decimal(value: &str) -> BigUint {
+    value.parse().expect("valid decimal constant")
+}
+
+/// Split a field element into four little-endian 96-bit limbs.
+fn u384_limbs(value: &BigUint) -> impl Iterator<Item = BigUint> + '_ {
+    let mask = (BigUint::from(1u32) << 96) - 1u32;
+    (0..4).map(move |i| (value >> (96 * i)) & &mask)
+}
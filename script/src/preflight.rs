@@ -0,0 +1,117 @@
+//! # Pre-flight Checks
+//!
+//! Guards against runaway executions and unexpectedly expensive proofs.
+//!
+//! The cost of a proof grows with the cycle count of the program, and a large
+//! input can make the guest loop billions of times. [`execute`] runs the program
+//! under an optional executor cycle limit, so such runs fail fast instead of
+//! hanging. Before starting a SNARK proof, the `starknet` binary runs a pre-flight
+//! execution with [`execute_within_budget`], which refuses to prove above a
+//! threshold unless `--force` is given, and prints its [`ProvingEstimate`]. The
+//! execution is capped just above the threshold, so a refused input costs no
+//! more cycles than an accepted one.
+
+use crate::error::{Error, Result};
+use crate::program::Program;
+use sp1_sdk::{EnvProver, ExecutionError, ExecutionReport, SP1PublicValues, SP1Stdin};
+
+/// Default cycle count above which SNARK proving requires `--force`.
+pub const DEFAULT_MAX_PROVE_CYCLES: u64 = 100_000_000;
+
+/// Number of cycles proven per shard by the SP1 prover with its default options.
+pub const SHARD_CYCLES: u64 = 1 << 21;
+
+/// Execute `program` on `stdin`, aborting after `cycle_limit` cycles if set.
+///
+/// Exceeding the limit fails with [`Error::Prover`], like any other execution
+/// failure.
+pub fn execute(
+    client: &EnvProver,
+    program: &Program,
+    stdin: &SP1Stdin,
+    cycle_limit: Option<u64>,
+) -> Result<(SP1PublicValues, ExecutionReport)> {
+    let mut builder = client.execute(&program.elf, stdin);
+    if let Some(cycle_limit) = cycle_limit {
+        builder = builder.cycle_limit(cycle_limit);
+    }
+    builder.run().map_err(Error::Prover)
+}
+
+/// Whether `error` is an execution aborted by its cycle limit.
+pub fn is_cycle_limit_error(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::Prover(error)
+            if matches!(
+                error.downcast_ref::<ExecutionError>(),
+                Some(ExecutionError::ExceededCycleLimit(_))
+            )
+    )
+}
+
+/// Execute `program` as the pre-flight check of a proof limited to `max_cycles`.
+///
+/// Unless `force` is set, the execution is aborted after `max_cycles + 1` cycles
+/// (or `cycle_limit`, if lower), and an execution reaching that cap or running
+/// for more than `max_cycles` cycles fails with [`Error::ProvingBudgetExceeded`].
+/// With `force`, only `cycle_limit` applies.
+pub fn execute_within_budget(
+    client: &EnvProver,
+    program: &Program,
+    stdin: &SP1Stdin,
+    cycle_limit: Option<u64>,
+    max_cycles: u64,
+    force: bool,
+) -> Result<(SP1PublicValues, ExecutionReport)> {
+    let budget_limit = (!force).then(|| max_cycles.saturating_add(1));
+    let limit = match (cycle_limit, budget_limit) {
+        (Some(cycle_limit), Some(budget_limit)) => Some(cycle_limit.min(budget_limit)),
+        (cycle_limit, budget_limit) => cycle_limit.or(budget_limit),
+    };
+    let (public_values, report) = match execute(client, program, stdin, limit) {
+        Err(error)
+            if budget_limit.is_some() && limit == budget_limit && is_cycle_limit_error(&error) =>
+        {
+            return Err(Error::ProvingBudgetExceeded {
+                cycles: max_cycles.saturating_add(1),
+                max_cycles,
+            });
+        }
+        result => result?,
+    };
+
+    let cycles = report.total_instruction_count();
+    if !force && cycles > max_cycles {
+        return Err(Error::ProvingBudgetExceeded { cycles, max_cycles });
+    }
+    Ok((public_values, report))
+}
+
+/// The expected size of a proof, derived from the cycle count of an execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProvingEstimate {
+    /// Number of cycles executed by the program.
+    pub cycles: u64,
+    /// Number of shards the execution is split into for proving.
+    pub shards: u64,
+}
+
+impl ProvingEstimate {
+    /// The estimate for an execution of `cycles` cycles.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use fibonacci_starknet::preflight::{ProvingEstimate, SHARD_CYCLES};
+    ///
+    /// assert_eq!(ProvingEstimate::new(1).shards, 1);
+    /// assert_eq!(ProvingEstimate::new(SHARD_CYCLES + 1).shards, 2);
+    /// ```
+    pub fn new(cycles: u64) -> Self {
+        Self {
+            cycles,
+            shards: cycles.div_ceil(SHARD_CYCLES).max(1),
+        }
+    }
+}
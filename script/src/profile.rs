@@ -0,0 +1,199 @@
+//! # Execution Profiles
+//!
+//! A breakdown of where a guest program spends its cycles, built from the
+//! `ExecutionReport` of an execution.
+//!
+//! Executing is cheap compared to proving, so profiles are the place to tune
+//! guest code before paying for a Groth16 proof. An [`ExecutionProfile`] holds
+//! the syscall counts, the precompile usage, the opcode histogram and the cost of
+//! every `cycle-tracker` section. It is saved as JSON and as section totals in
+//! folded format (`{name}-sections.folded`). The execution report only records
+//! the total cycles of each section, so that file is not a call trace: it holds
+//! one line per section and no stacks below them.
+//!
+//! [`cycle_regions`] lists the cost of every named region a guest reports. The
+//! Fibonacci program has no markers: they would change its ELF, and with it the
+//! verification key the contracts and fixtures are bound to.
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sp1_sdk::ExecutionReport;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Directory profiles are written to by default.
+///
+/// Like [`crate::fixture::default_fixture_dir`], this path is resolved at
+/// compile time and only makes sense in a checkout of the repository.
+pub fn default_profile_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../target/profile")
+}
+
+/// Cycles spent in a `cycle-tracker` region of the guest program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleRegion {
+    /// Name of the region, as printed by the guest.
+    pub name: String,
+    /// Cycles spent in the region, over all invocations.
+    pub cycles: u64,
+    /// Number of times the region was entered.
+    pub invocations: u64,
+}
+
+/// The `cycle-tracker` regions of an execution.
+///
+/// Regions are listed in alphabetical order. Regions that were never entered are
+/// omitted, so programs without markers yield an empty list.
+pub fn cycle_regions(report: &ExecutionReport) -> Vec<CycleRegion> {
+    let mut names: Vec<&str> = report.cycle_tracker.keys().map(String::as_str).collect();
+    names.sort_unstable();
+
+    names
+        .into_iter()
+        .map(|name| CycleRegion {
+            name: name.to_string(),
+            cycles: report.cycle_tracker[name],
+            invocations: report.invocation_tracker.get(name).copied().unwrap_or(1),
+        })
+        .collect()
+}
+
+/// Cycle and syscall breakdown of a single execution.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionProfile {
+    /// Name of the executed program.
+    pub program: String,
+    /// Total number of executed RISC-V instructions (cycles).
+    pub total_cycles: u64,
+    /// Total number of syscalls.
+    pub total_syscalls: u64,
+    /// Number of calls of every syscall that was used.
+    pub syscalls: BTreeMap<String, u64>,
+    /// The subset of [`ExecutionProfile::syscalls`] handled by precompile chips.
+    pub precompiles: BTreeMap<String, u64>,
+    /// Number of executions of every opcode that was used.
+    pub opcodes: BTreeMap<String, u64>,
+    /// Cycles spent in every `cycle-tracker` section, over all invocations.
+    pub sections: BTreeMap<String, u64>,
+    /// Number of invocations of every `cycle-tracker-report` section.
+    pub section_invocations: BTreeMap<String, u64>,
+    /// Number of distinct memory addresses touched by the program.
+    pub touched_memory_addresses: u64,
+}
+
+/// Paths of the files written by [`ExecutionProfile::write`].
+#[derive(Debug, Clone)]
+pub struct ProfileFiles {
+    /// The JSON profile (`{name}-profile.json`).
+    pub json_path: PathBuf,
+    /// The section totals in folded format (`{name}-sections.folded`).
+    pub sections_path: PathBuf,
+}
+
+impl ExecutionProfile {
+    /// Build the profile of an execution of `program`.
+    pub fn from_report(program: &str, report: &ExecutionReport) -> Self {
+        let mut syscalls = BTreeMap::new();
+        let mut precompiles = BTreeMap::new();
+        for (code, &count) in report.syscall_counts.iter() {
+            if count == 0 {
+                continue;
+            }
+            let name = format!("{code:?}");
+            if code.should_send() == 1 {
+                precompiles.insert(name.clone(), count);
+            }
+            syscalls.insert(name, count);
+        }
+
+        let opcodes = report
+            .opcode_counts
+            .iter()
+            .filter(|(_, &count)| count > 0)
+            .map(|(opcode, &count)| (opcode.to_string(), count))
+            .collect();
+
+        Self {
+            program: program.to_string(),
+            total_cycles: report.total_instruction_count(),
+            total_syscalls: report.total_syscall_count(),
+            syscalls,
+            precompiles,
+            opcodes,
+            sections: report.cycle_tracker.clone().into_iter().collect(),
+            section_invocations: report.invocation_tracker.clone().into_iter().collect(),
+            touched_memory_addresses: report.touched_memory_addresses,
+        }
+    }
+
+    /// The `count` most frequent entries of a histogram, most frequent first.
+    pub fn top(histogram: &BTreeMap<String, u64>, count: usize) -> Vec<(&str, u64)> {
+        let mut entries: Vec<_> = histogram
+            .iter()
+            .map(|(name, &value)| (name.as_str(), value))
+            .collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        entries.truncate(count);
+        entries
+    }
+
+    /// The section totals in folded format, one `program;section cycles` line
+    /// per `cycle-tracker` section.
+    ///
+    /// The cycles outside any section are attributed to an `untracked` section.
+    /// Nested sections are reported as siblings, since the execution report does
+    /// not record their nesting; their cycles are then also counted in their
+    /// parent. This is a per-section summary, not a trace of the execution.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use fibonacci_starknet::profile::ExecutionProfile;
+    ///
+    /// let mut profile = ExecutionProfile {
+    ///     program: "fibonacci-program".to_string(),
+    ///     total_cycles: 100,
+    ///     ..Default::default()
+    /// };
+    /// profile.sections.insert("compute".to_string(), 60);
+    ///
+    /// assert_eq!(
+    ///     profile.folded_section_totals(),
+    ///     "fibonacci-program;compute 60\nfibonacci-program;untracked 40\n"
+    /// );
+    /// ```
+    pub fn folded_section_totals(&self) -> String {
+        let frame = |name: &str| name.replace([';', ' '], "_");
+        let root = frame(&self.program);
+
+        let mut folded = String::new();
+        for (section, cycles) in &self.sections {
+            folded += &format!("{root};{} {cycles}\n", frame(section));
+        }
+
+        let tracked: u64 = self.sections.values().sum();
+        let untracked = self.total_cycles.saturating_sub(tracked);
+        if untracked > 0 {
+            folded += &format!("{root};untracked {untracked}\n");
+        }
+        folded
+    }
+
+    /// Write the JSON profile and the folded section totals to `out_dir`.
+    pub fn write(&self, out_dir: &Path, name: &str) -> Result<ProfileFiles> {
+        std::fs::create_dir_all(out_dir).map_err(Error::fixture_io(out_dir))?;
+
+        let json_path = out_dir.join(format!("{name}-profile.json"));
+        std::fs::write(&json_path, serde_json::to_string_pretty(self)?)
+            .map_err(Error::fixture_io(&json_path))?;
+
+        let sections_path = out_dir.join(format!("{name}-sections.folded"));
+        std::fs::write(&sections_path, self.folded_section_totals())
+            .map_err(Error::fixture_io(&sections_path))?;
+
+        Ok(ProfileFiles {
+            json_path,
+            sections_path,
+        })
+    }
+}
@@ -0,0 +1,106 @@
+//! # SP1 Programs
+//!
+//! Guest programs to prove, either the Fibonacci program embedded at build time
+//! ([`FIBONACCI_ELF`]) or ELF files loaded from disk at run time.
+
+use crate::error::{Error, Result};
+use crate::FIBONACCI_ELF;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+/// Build target name of the embedded Fibonacci program.
+pub const FIBONACCI_PROGRAM_NAME: &str = "fibonacci-program";
+
+/// Directory where `sp1-build` places the guest ELFs of this workspace.
+///
+/// Like [`crate::fixture::default_fixture_dir`], this path is resolved at compile
+/// time and only makes sense in a checkout of the repository.
+pub fn default_elf_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../target/elf-compilation/riscv32im-succinct-zkvm-elf/release")
+}
+
+/// An SP1 guest program.
+#[derive(Debug, Clone)]
+pub struct Program {
+    /// Name of the program, e.g. its build target name.
+    pub name: String,
+    /// The RISC-V ELF executed by the zkVM.
+    pub elf: Cow<'static, [u8]>,
+}
+
+impl Program {
+    /// The Fibonacci program embedded in this crate.
+    pub fn fibonacci() -> Self {
+        Self {
+            name: FIBONACCI_PROGRAM_NAME.to_string(),
+            elf: Cow::Borrowed(FIBONACCI_ELF),
+        }
+    }
+
+    /// The program selected by an `--elf` option.
+    ///
+    /// Loads the ELF at `path` when given, and falls back to the embedded
+    /// Fibonacci program otherwise.
+    pub fn from_elf_arg(path: Option<&Path>) -> Result<Self> {
+        match path {
+            Some(path) => Self::from_path(path),
+            None => Ok(Self::fibonacci()),
+        }
+    }
+
+    /// Whether this is the Fibonacci program embedded at build time.
+    pub fn is_embedded(&self) -> bool {
+        matches!(self.elf, Cow::Borrowed(_))
+    }
+
+    /// Load an ELF from disk, named after its file stem.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::FixtureIo`] if the file cannot be read.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Self::named(name, path)
+    }
+
+    /// Load an ELF from disk under the given name.
+    pub fn named(name: impl Into<String>, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let elf = std::fs::read(path).map_err(Error::fixture_io(path))?;
+        Ok(Self {
+            name: name.into(),
+            elf: Cow::Owned(elf),
+        })
+    }
+
+    /// Resolve a program from a command-line specification.
+    ///
+    /// The specification is one of:
+    /// - `NAME=PATH`: the ELF at `PATH`, registered as `NAME`
+    /// - `PATH`: an existing ELF file, named after its file stem
+    /// - `TARGET`: a guest build target of this workspace, looked up in
+    ///   [`default_elf_dir`]; `fibonacci-program` is the embedded ELF
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        if let Some((name, path)) = spec.split_once('=') {
+            return Self::named(name, path);
+        }
+        if Path::new(spec).is_file() {
+            return Self::from_path(spec);
+        }
+        if spec == FIBONACCI_PROGRAM_NAME {
+            return Ok(Self::fibonacci());
+        }
+        Self::named(spec, default_elf_dir().join(spec))
+    }
+
+    /// The `0x`-prefixed SHA-256 digest of the ELF.
+    pub fn elf_sha256(&self) -> String {
+        format!("0x{}", hex::encode(Sha256::digest(&self.elf)))
+    }
+}
@@ -0,0 +1,188 @@
+//! # Program Registry
+//!
+//! A single source of truth for the SP1 programs a Starknet application accepts.
+//!
+//! The `vkey` binary writes the registry twice, from the same data:
+//!
+//! - `program_registry.json`: name, verification key hash, ELF SHA-256 and SP1
+//!   version of every program, for off-chain services
+//! - `program_registry.cairo`: one `u256` constant per program plus an
+//!   `is_registered_program` allowlist check, for the contracts
+
+use crate::error::{Error, Result};
+use crate::program::Program;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{HashableKey, SP1VerifyingKey};
+use std::path::{Path, PathBuf};
+
+/// File name of the JSON registry.
+pub const REGISTRY_JSON_FILE_NAME: &str = "program_registry.json";
+
+/// File name of the generated Cairo registry module.
+pub const REGISTRY_CAIRO_FILE_NAME: &str = "program_registry.cairo";
+
+/// A registered SP1 program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramEntry {
+    /// Name of the program, e.g. its build target name.
+    pub name: String,
+    /// The program verification key hash (`vk.bytes32()`).
+    pub vkey: String,
+    /// The `0x`-prefixed SHA-256 digest of the program ELF.
+    pub elf_sha256: String,
+    /// The SP1 circuit version the verification key was derived with.
+    pub sp1_version: String,
+}
+
+impl ProgramEntry {
+    /// Describe a program and its verification key.
+    pub fn new(program: &Program, vk: &SP1VerifyingKey, sp1_version: &str) -> Self {
+        Self {
+            name: program.name.clone(),
+            vkey: vk.bytes32(),
+            elf_sha256: program.elf_sha256(),
+            sp1_version: sp1_version.to_string(),
+        }
+    }
+
+    /// Name of the Cairo constant holding the verification key,
+    /// e.g. `FIBONACCI_PROGRAM` for `fibonacci-program`.
+    pub fn cairo_const_name(&self) -> String {
+        let name: String = self
+            .name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            name
+        } else {
+            format!("PROGRAM_{name}")
+        }
+    }
+}
+
+/// The programs accepted by a Starknet application.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramRegistry {
+    /// The registered programs, in command-line order.
+    pub programs: Vec<ProgramEntry>,
+}
+
+/// Paths of the files written by [`ProgramRegistry::write`].
+#[derive(Debug, Clone)]
+pub struct RegistryFiles {
+    /// Path of the JSON registry.
+    pub json_path: PathBuf,
+    /// Path of the generated Cairo module.
+    pub cairo_path: PathBuf,
+}
+
+impl ProgramRegistry {
+    /// Add a program to the registry.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidRegistry`] if another program already uses the
+    /// same name or Cairo constant name.
+    pub fn push(&mut self, entry: ProgramEntry) -> Result<()> {
+        let const_name = entry.cairo_const_name();
+        if let Some(existing) = self
+            .programs
+            .iter()
+            .find(|existing| existing.cairo_const_name() == const_name)
+        {
+            return Err(Error::InvalidRegistry(format!(
+                "programs `{}` and `{}` would both be named {const_name}",
+                existing.name, entry.name
+            )));
+        }
+        self.programs.push(entry);
+        Ok(())
+    }
+
+    /// Contents of the generated Cairo registry module.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use fibonacci_starknet::registry::{ProgramEntry, ProgramRegistry};
+    ///
+    /// let mut registry = ProgramRegistry::default();
+    /// registry
+    ///     .push(ProgramEntry {
+    ///         name: "fibonacci-program".to_string(),
+    ///         vkey: "0x00ee2a4a1c9c659ed802a544aa469136e72e1a1538af94fce56705576b48f247".to_string(),
+    ///         elf_sha256: "0x00".to_string(),
+    ///         sp1_version: "v4.0.0-rc.3".to_string(),
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let module = registry.cairo_module();
+    /// assert!(module.contains("pub const FIBONACCI_PROGRAM: u256 = 0x00ee2a4a"));
+    /// assert!(module.contains("vkey == FIBONACCI_PROGRAM"));
+    /// ```
+    pub fn cairo_module(&self) -> String {
+        let mut module = String::from(
+            "// @generated by `cargo run --release --bin vkey -- --program <PROGRAM>...` in the
+// script directory. Do not edit by hand: see program_registry.json for the ELF
+// digests and SP1 version of every program.
+",
+        );
+
+        for entry in &self.programs {
+            module += &format!(
+                "
+/// Verification key of the `{}` SP1 program.
+pub const {}: u256 = {};
+",
+                entry.name,
+                entry.cairo_const_name(),
+                entry.vkey
+            );
+        }
+
+        let checks = if self.programs.is_empty() {
+            "false".to_string()
+        } else {
+            self.programs
+                .iter()
+                .map(|entry| format!("vkey == {}", entry.cairo_const_name()))
+                .collect::<Vec<_>>()
+                .join("\n        || ")
+        };
+        module += &format!(
+            "
+/// Whether `vkey` belongs to one of the registered SP1 programs.
+pub fn is_registered_program(vkey: u256) -> bool {{
+    {checks}
+}}
+"
+        );
+
+        module
+    }
+
+    /// Write the JSON registry and the Cairo module to `out_dir`.
+    pub fn write(&self, out_dir: &Path) -> Result<RegistryFiles> {
+        std::fs::create_dir_all(out_dir).map_err(Error::fixture_io(out_dir))?;
+
+        let json_path = out_dir.join(REGISTRY_JSON_FILE_NAME);
+        std::fs::write(&json_path, serde_json::to_string_pretty(self)?)
+            .map_err(Error::fixture_io(&json_path))?;
+
+        let cairo_path = out_dir.join(REGISTRY_CAIRO_FILE_NAME);
+        std::fs::write(&cairo_path, self.cairo_module()).map_err(Error::fixture_io(&cairo_path))?;
+
+        Ok(RegistryFiles {
+            json_path,
+            cairo_path,
+        })
+    }
+}
@@ -0,0 +1,176 @@
+//! # Run Reports
+//!
+//! A machine-readable summary of a proving session, for CI.
+//!
+//! The binaries print human-oriented progress messages. With `--report <FILE>`
+//! or `--json`, they also emit a [`RunReport`]: the input, the public values
+//! (decoded with [`PublicValuesStruct`] when possible), the cycle count and its
+//! split by `cycle-tracker` region, the duration of every stage, the proof size,
+//! the calldata length and the files written.
+
+use crate::error::{Error, Result};
+use crate::fixture::{FixtureOutput, ProofSystem};
+use crate::input::ProgramInput;
+use crate::profile::{cycle_regions, CycleRegion};
+use crate::program::Program;
+use alloy_sol_types::SolType;
+use fibonacci_lib::PublicValuesStruct;
+use serde::Serialize;
+use sp1_sdk::{ExecutionReport, SP1ProofWithPublicValues};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// The public values of the Fibonacci program, decoded from their ABI encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DecodedPublicValues {
+    /// The input number.
+    pub n: u32,
+    /// The `n-1`th Fibonacci number.
+    pub a: u32,
+    /// The `n`th Fibonacci number.
+    pub b: u32,
+}
+
+impl DecodedPublicValues {
+    /// Decode ABI-encoded [`PublicValuesStruct`] bytes.
+    ///
+    /// Returns `None` for public values of other programs.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        let PublicValuesStruct { n, a, b } = PublicValuesStruct::abi_decode(bytes).ok()?;
+        Some(Self { n, a, b })
+    }
+}
+
+/// Wall-clock duration of each stage of a run, in milliseconds.
+///
+/// Stages that did not run are `null`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct StageDurations {
+    /// Program setup (`client.setup`), including key cache lookups.
+    pub setup_ms: Option<u64>,
+    /// Program execution, without proving.
+    pub execute_ms: Option<u64>,
+    /// Proof generation, including proof cache lookups.
+    pub prove_ms: Option<u64>,
+    /// Calldata conversion and fixture writing.
+    pub convert_ms: Option<u64>,
+}
+
+/// Summary of a single run of a binary.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RunReport {
+    /// The operation performed, e.g. `execute`, `prove` or `convert`.
+    pub command: String,
+    /// Name of the program.
+    pub program: Option<String>,
+    /// The `0x`-prefixed SHA-256 digest of the program ELF.
+    pub elf_sha256: Option<String>,
+    /// Lowercase name of the proof system, e.g. `groth16`.
+    pub system: Option<String>,
+    /// The program input.
+    pub input: Option<ProgramInput>,
+    /// The program verification key hash.
+    pub vkey: Option<String>,
+    /// The public values committed by the program (hex string).
+    pub public_values: Option<String>,
+    /// The public values decoded as a [`PublicValuesStruct`], if they are one.
+    pub decoded_public_values: Option<DecodedPublicValues>,
+    /// Number of RISC-V cycles executed by the program.
+    pub cycles: Option<u64>,
+    /// Cycles spent in each `cycle-tracker` region of the program.
+    pub regions: Vec<CycleRegion>,
+    /// Duration of every stage of the run.
+    pub durations: StageDurations,
+    /// Size of the proof in bytes: the on-chain encoding for SNARKs, the
+    /// bincode encoding for STARKs.
+    pub proof_size: Option<usize>,
+    /// Number of Starknet calldata elements.
+    pub calldata_len: Option<usize>,
+    /// Whether the proof was taken from the proof cache.
+    pub cached_proof: bool,
+    /// Whether the fixture is a mock fixture generated without proving.
+    pub mock: bool,
+    /// The files written by the run.
+    pub files: Vec<PathBuf>,
+}
+
+impl RunReport {
+    /// An empty report for `command`.
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Record the program of the run.
+    pub fn set_program(&mut self, program: &Program) {
+        self.program = Some(program.name.clone());
+        self.elf_sha256 = Some(program.elf_sha256());
+    }
+
+    /// Record the public values, decoding them when possible.
+    pub fn set_public_values(&mut self, bytes: &[u8]) {
+        self.public_values = Some(format!("0x{}", hex::encode(bytes)));
+        self.decoded_public_values = DecodedPublicValues::decode(bytes);
+    }
+
+    /// Record the cycle counts of an execution of the program.
+    pub fn set_execution(&mut self, report: &ExecutionReport) {
+        self.cycles = Some(report.total_instruction_count());
+        self.regions = cycle_regions(report);
+    }
+
+    /// Record a generated proof of the given system.
+    pub fn set_proof(&mut self, proof: &SP1ProofWithPublicValues, system: ProofSystem) {
+        self.system = Some(system.name().to_string());
+        self.set_public_values(proof.public_values.as_slice());
+        self.proof_size = if system.is_onchain_verifiable() {
+            Some(proof.bytes().len())
+        } else {
+            bincode::serialized_size(&proof.proof)
+                .ok()
+                .map(|size| size as usize)
+        };
+    }
+
+    /// Record the fixture, calldata and files written for the run.
+    pub fn set_fixture(&mut self, output: &FixtureOutput) {
+        self.vkey = Some(output.fixture.vkey.clone());
+        self.mock = output.fixture.mock;
+        if let Ok(bytes) = output.fixture.public_values_bytes() {
+            self.set_public_values(&bytes);
+        }
+        self.calldata_len = output.calldata_len;
+
+        if let Some(files) = &output.files {
+            self.files.push(files.json_path.clone());
+            self.files.extend(files.calldata_path.iter().cloned());
+            if let Some(artifacts) = &files.artifacts {
+                self.files.push(artifacts.proof_path.clone());
+                self.files.push(artifacts.vk_path.clone());
+            }
+        }
+    }
+
+    /// The report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Write the report to `path` as JSON.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(Error::fixture_io(dir))?;
+        }
+        std::fs::write(path, self.to_json()?).map_err(Error::fixture_io(path))
+    }
+}
+
+/// Run `f`, returning its result and the elapsed time in milliseconds.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, u64) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed().as_millis() as u64)
+}
@@ -0,0 +1,58 @@
+//! # Proof Wrapping
+//!
+//! Lifting compressed STARK proofs to Groth16 after the fact.
+//!
+//! `starknet --system compressed` skips the SNARK wrapping step, which dominates
+//! proving time for small programs. Once a compressed proof is worth publishing,
+//! [`wrap_groth16`] runs the remaining steps of the SP1 Groth16 pipeline on it,
+//! producing the same proof `client.prove(..).groth16()` would have returned.
+//!
+//! The SDK only exposes these steps on the local prover, so wrapping always runs
+//! on this machine, whatever `SP1_PROVER` selects.
+
+use crate::error::{Error, Result};
+use crate::fixture::ProofSystem;
+use sp1_sdk::install::try_install_circuit_artifacts;
+use sp1_sdk::{CpuProver, Prover, SP1Proof, SP1ProofWithPublicValues};
+
+/// Wrap a compressed proof into a Groth16 proof verifiable on Starknet.
+///
+/// The proof is shrunk, wrapped into a BN254-friendly STARK and finally proven
+/// with the Groth16 circuit. The circuit artifacts are downloaded on first use,
+/// like for a regular Groth16 proof. The public values and SP1 version of the
+/// input proof are kept.
+///
+/// ## Parameters
+///
+/// - `prover`: A local prover, i.e. `ProverClient::builder().cpu().build()`
+/// - `proof`: A compressed proof, usually loaded with [`crate::artifacts::load_proof`]
+///
+/// ## Errors
+///
+/// Returns [`Error::NotWrappable`] for anything but a compressed proof and
+/// [`Error::Prover`] if one of the recursion steps fails.
+pub fn wrap_groth16(
+    prover: &CpuProver,
+    mut proof: SP1ProofWithPublicValues,
+) -> Result<SP1ProofWithPublicValues> {
+    let SP1Proof::Compressed(reduce_proof) = proof.proof else {
+        return Err(Error::NotWrappable(
+            ProofSystem::of_proof(&proof.proof).name(),
+        ));
+    };
+
+    // Shrink the recursion proof and wrap it into a STARK over the BN254 field
+    let inner = prover.inner();
+    let shrunk = inner
+        .shrink(*reduce_proof, Default::default())
+        .map_err(|error| Error::Prover(error.into()))?;
+    let outer = inner
+        .wrap_bn254(shrunk, Default::default())
+        .map_err(|error| Error::Prover(error.into()))?;
+
+    // Prove the outer STARK with the Groth16 circuit
+    let artifacts = try_install_circuit_artifacts("groth16");
+    proof.proof = SP1Proof::Groth16(inner.wrap_groth16_bn254(outer, &artifacts));
+
+    Ok(proof)
+}
@@ -0,0 +1,93 @@
+//! # Checked-in Calldata Fixture
+//!
+//! Decodes `contracts/src/fixtures/groth16-calldata.txt`, generated by Garaga for
+//! a real Groth16 proof, and checks it against `groth16-fixture.json`. This pins
+//! [`decode_groth16_calldata`] to the actual Garaga SP1 Groth16 layout, down to
+//! the MPCheck and MSM hint blocks.
+//!
+//! ```bash
+//! cargo test --release --test calldata
+//! ```
+
+use fibonacci_starknet::decode::{decode_groth16_calldata, parse_hex_calldata, DecodedCalldata};
+use fibonacci_starknet::fixture::default_fixture_dir;
+use fibonacci_starknet::SP1FibonacciProofFixture;
+
+/// Number of felts of the checked-in calldata.
+const CALLDATA_LEN: usize = 2041;
+
+/// Index of the first hint felt: span length, A, B, C, vkey and three public
+/// value words.
+const HINTS_OFFSET: usize = 1 + 8 + 16 + 8 + 2 + (1 + 8 * 3);
+
+/// Read and decode the checked-in calldata, returning it with its felt count.
+fn decode_checked_in_calldata() -> (DecodedCalldata, usize) {
+    let path = default_fixture_dir().join("groth16-calldata.txt");
+    let text = std::fs::read_to_string(&path).expect("failed to read the calldata fixture");
+    let calldata = parse_hex_calldata(&text).expect("invalid hex calldata");
+    let decoded = decode_groth16_calldata(&calldata).expect("failed to decode calldata");
+    (decoded, calldata.len())
+}
+
+#[test]
+fn checked_in_calldata_matches_fixture() {
+    let (decoded, _) = decode_checked_in_calldata();
+    let fixture =
+        SP1FibonacciProofFixture::load(default_fixture_dir().join("groth16-fixture.json"))
+            .expect("failed to load the JSON fixture");
+
+    let checks = decoded.check_fixture(&fixture).expect("invalid fixture");
+    assert_eq!(
+        checks.len(),
+        10,
+        "expected vkey, public values and 8 coordinates"
+    );
+    for check in checks {
+        assert!(
+            check.ok,
+            "`{}` does not match the fixture: expected {}, found {}",
+            check.section, check.expected, check.actual
+        );
+    }
+}
+
+#[test]
+fn checked_in_calldata_hint_blocks() {
+    let (decoded, len) = decode_checked_in_calldata();
+    assert_eq!(len, CALLDATA_LEN);
+    assert_eq!(decoded.span_len, CALLDATA_LEN - 1);
+    assert_eq!(decoded.public_inputs.len(), 3);
+
+    let hints = &decoded.hints;
+    assert_eq!(hints.offset, HINTS_OFFSET);
+    assert_eq!(hints.offset + hints.len, CALLDATA_LEN);
+    assert_eq!(hints.mpcheck.offset, HINTS_OFFSET);
+    assert_eq!(hints.msm.offset, hints.mpcheck.offset + hints.mpcheck.len);
+
+    let blocks: Vec<_> = hints
+        .mpcheck
+        .blocks
+        .iter()
+        .map(|block| (block.name, block.len, block.items))
+        .collect();
+    assert_eq!(
+        blocks,
+        [
+            ("lambda_root", 36, None),
+            ("lambda_root_inverse", 36, None),
+            ("w", 18, None),
+            ("ris", 1 + 35 * 36, Some(35)),
+            ("big_q", 1 + 190 * 3, Some(190)),
+            ("z", 1, None),
+            ("small_q", 33, None),
+        ]
+    );
+
+    let msm: Vec<_> = hints
+        .msm
+        .blocks
+        .iter()
+        .map(|block| (block.name, block.len, block.items))
+        .collect();
+    assert_eq!(msm, [("msm_hint", 1 + 24, Some(24))]);
+}
@@ -0,0 +1,129 @@
+//! # Differential Tests of the Guest Program
+//!
+//! Executes the embedded Fibonacci ELF on random and boundary inputs and checks
+//! the committed [`PublicValuesStruct`] against [`fibonacci_lib::fibonacci`].
+//!
+//! Executions are capped at [`CYCLE_LIMIT`] cycles. Most generated inputs are
+//! large but still complete within the limit, and a few go up to `u32::MAX` loop
+//! iterations to check that such runs fail with the cycle-limit error instead of
+//! running for hours. Failing inputs are shrunk by proptest to a minimal
+//! counterexample.
+//!
+//! ```bash
+//! cargo test --release --test differential
+//! ```
+
+use alloy_sol_types::SolType;
+use fibonacci_lib::{fibonacci, FibonacciInput, PublicValuesStruct};
+use fibonacci_starknet::input::ProgramInput;
+use fibonacci_starknet::FIBONACCI_ELF;
+use proptest::prelude::*;
+use sp1_sdk::{CpuProver, ExecutionError, ProverClient, SP1PublicValues};
+
+/// Maximum number of cycles of a single execution.
+const CYCLE_LIMIT: u64 = 1_000_000;
+
+/// Upper bound on the cycles of one iteration of the guest's Fibonacci loop.
+const MAX_CYCLES_PER_ITERATION: u64 = 8;
+
+/// Upper bound on the cycles spent outside the Fibonacci loop.
+const MAX_OVERHEAD_CYCLES: u64 = 200_000;
+
+/// Largest input of the small inputs strategy.
+const SMALL_INPUTS: u32 = 10_000;
+
+/// Largest input that always completes within [`CYCLE_LIMIT`].
+const ALWAYS_WITHIN_LIMIT: u32 =
+    ((CYCLE_LIMIT - MAX_OVERHEAD_CYCLES) / MAX_CYCLES_PER_ITERATION) as u32;
+
+/// Smallest input that always exceeds [`CYCLE_LIMIT`], at one cycle per iteration.
+const ALWAYS_EXCEEDS_LIMIT: u32 = CYCLE_LIMIT as u32;
+
+/// Inputs around the last Fibonacci numbers that fit in a `u32` (F(47)), where
+/// the guest starts relying on wrapping additions.
+const BOUNDARY_INPUTS: [u32; 6] = [0, 1, 2, 46, 47, 48];
+
+/// Execute the guest on `n`, returning its public values.
+fn execute(client: &CpuProver, n: u32) -> anyhow::Result<SP1PublicValues> {
+    let stdin = ProgramInput::Fibonacci(FibonacciInput { n }).to_stdin();
+    let (public_values, _) = client
+        .execute(FIBONACCI_ELF, &stdin)
+        .cycle_limit(CYCLE_LIMIT)
+        .run()?;
+    Ok(public_values)
+}
+
+/// Whether `error` is an execution aborted by [`CYCLE_LIMIT`].
+fn is_cycle_limit_error(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<ExecutionError>(),
+        Some(ExecutionError::ExceededCycleLimit(_))
+    )
+}
+
+/// The `(n, a, b)` public values the guest is expected to commit for `n`.
+fn expected(n: u32) -> (u32, u32, u32) {
+    let (a, b) = fibonacci(n);
+    (n, a, b)
+}
+
+/// Decode the `(n, a, b)` public values committed by the guest.
+fn decode(public_values: &SP1PublicValues) -> (u32, u32, u32) {
+    let PublicValuesStruct { n, a, b } =
+        PublicValuesStruct::abi_decode(public_values.as_slice()).expect("invalid public values");
+    (n, a, b)
+}
+
+/// Small and large inputs completing within the limit, boundary inputs and a few
+/// inputs exceeding the limit.
+fn inputs() -> impl Strategy<Value = u32> {
+    prop_oneof![
+        3 => 0..=SMALL_INPUTS,
+        4 => SMALL_INPUTS..=ALWAYS_WITHIN_LIMIT,
+        2 => proptest::sample::select(BOUNDARY_INPUTS.to_vec()),
+        1 => ALWAYS_EXCEEDS_LIMIT..=u32::MAX,
+    ]
+}
+
+#[test]
+fn boundary_inputs_match_library() {
+    let client = ProverClient::builder().cpu().build();
+    for n in BOUNDARY_INPUTS {
+        let public_values = execute(&client, n).expect("failed to execute program");
+        assert_eq!(decode(&public_values), expected(n), "mismatch for n = {n}");
+    }
+}
+
+#[test]
+fn large_inputs_hit_cycle_limit() {
+    let client = ProverClient::builder().cpu().build();
+    for n in [ALWAYS_EXCEEDS_LIMIT, u32::MAX] {
+        match execute(&client, n) {
+            Ok(_) => panic!("execution within the cycle limit for n = {n}"),
+            Err(error) => assert!(
+                is_cycle_limit_error(&error),
+                "unexpected error for n = {n}: {error:#}"
+            ),
+        }
+    }
+}
+
+#[test]
+fn guest_matches_library() {
+    let client = ProverClient::builder().cpu().build();
+    proptest!(ProptestConfig::with_cases(64), |(n in inputs())| {
+        match execute(&client, n) {
+            Ok(public_values) => {
+                prop_assert!(n < ALWAYS_EXCEEDS_LIMIT, "no cycle limit error for n = {}", n);
+                prop_assert_eq!(decode(&public_values), expected(n));
+            }
+            // Only inputs beyond ALWAYS_WITHIN_LIMIT may exhaust the cycle limit
+            Err(error) => prop_assert!(
+                n > ALWAYS_WITHIN_LIMIT && is_cycle_limit_error(&error),
+                "execution failed for n = {}: {:#}",
+                n,
+                error
+            ),
+        }
+    });
+}